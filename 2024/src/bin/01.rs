@@ -1,7 +1,7 @@
-use std::collections::HashMap;
-
 advent_of_code::solution!(1);
 
+use advent_of_code::counter::Counter;
+
 pub fn part_one(input: &str) -> Option<u32> {
     // Split the input into lines
     let lines = input.lines();
@@ -43,14 +43,11 @@ pub fn part_two(input: &str) -> Option<u32> {
         .unzip();
 
     // Create a count map for how many times each number appears in the right list
-    let mut counter = HashMap::new();
-    for r in right {
-        *counter.entry(r).or_insert(0) += 1;
-    }
+    let counter = right.into_iter().collect::<Counter<_>>();
 
     let result = left
         .iter()
-        .map(|l| u32::try_from(l * *counter.get(l).unwrap_or(&0)).unwrap())
+        .map(|l| u32::try_from(u64::try_from(*l).unwrap() * counter.get(l)).unwrap())
         .reduce(|acc, v| acc + v);
 
     result
@@ -63,12 +60,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(31));
     }
 }
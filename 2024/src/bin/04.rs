@@ -1,7 +1,7 @@
-use std::collections::HashMap;
-
 advent_of_code::solution!(4);
 
+use advent_of_code::hash::FastMap;
+
 fn get_neighbors(x: usize, y: usize, bx: usize, by: usize) -> Vec<Vec<(usize, usize)>> {
     let mut results = vec![];
     if y + 3 < by {
@@ -68,7 +68,7 @@ fn get_neighbors(x: usize, y: usize, bx: usize, by: usize) -> Vec<Vec<(usize, us
         results.push(br_diagonal);
     }
 
-    return results;
+    results
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -77,7 +77,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|line| line.chars().collect::<Vec<char>>())
         .collect::<Vec<Vec<char>>>();
 
-    let xmas_index: HashMap<usize, char> = [(0, 'X'), (1, 'M'), (2, 'A'), (3, 'S')]
+    let xmas_index: FastMap<usize, char> = [(0, 'X'), (1, 'M'), (2, 'A'), (3, 'S')]
         .into_iter()
         .collect();
 
@@ -87,15 +87,10 @@ pub fn part_one(input: &str) -> Option<u32> {
             if val == &'X' {
                 let neighbors = get_neighbors(x, y, map.len(), row.len());
                 for neighbor in neighbors {
-                    if neighbor
-                        .iter()
-                        .enumerate()
-                        .map(|(i, (r, c))| {
-                            let character = map[*r][*c];
-                            return &character == xmas_index.get(&i).unwrap();
-                        })
-                        .all(|pred| pred)
-                    {
+                    if neighbor.iter().enumerate().all(|(i, (r, c))| {
+                        let character = map[*r][*c];
+                        &character == xmas_index.get(&i).unwrap()
+                    }) {
                         result += 1
                     }
                 }
@@ -106,12 +101,10 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-fn get_neighbors_two(
-    x: usize,
-    y: usize,
-    bx: usize,
-    by: usize,
-) -> Option<Vec<(Vec<(usize, usize)>, Vec<(usize, usize)>)>> {
+/// The two diagonals crossing at a cell.
+type Cross = (Vec<(usize, usize)>, Vec<(usize, usize)>);
+
+fn get_neighbors_two(x: usize, y: usize, bx: usize, by: usize) -> Option<Vec<Cross>> {
     if x > 0 && x + 1 < bx && y > 0 && y + 1 < by {
         Some(vec![
             (
@@ -142,7 +135,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .map(|line| line.chars().collect::<Vec<char>>())
         .collect::<Vec<Vec<char>>>();
 
-    let xmas_index: HashMap<usize, char> = [(0, 'M'), (1, 'A'), (2, 'S')].into_iter().collect();
+    let xmas_index: FastMap<usize, char> = [(0, 'M'), (1, 'A'), (2, 'S')].into_iter().collect();
 
     let mut result = 0;
     for (x, row) in map.iter().enumerate() {
@@ -150,23 +143,13 @@ pub fn part_two(input: &str) -> Option<u32> {
             if val == &'A' {
                 if let Some(diagonals) = get_neighbors_two(x, y, map.len(), row.len()) {
                     for (one_diagonal, two_diagonal) in diagonals {
-                        if one_diagonal
-                            .iter()
-                            .enumerate()
-                            .map(|(i, (r, c))| {
-                                let character = map[*r][*c];
-                                return &character == xmas_index.get(&i).unwrap();
-                            })
-                            .all(|pred| pred)
-                            && two_diagonal
-                                .iter()
-                                .enumerate()
-                                .map(|(i, (r, c))| {
-                                    let character = map[*r][*c];
-                                    return &character == xmas_index.get(&i).unwrap();
-                                })
-                                .all(|pred| pred)
-                        {
+                        if one_diagonal.iter().enumerate().all(|(i, (r, c))| {
+                            let character = map[*r][*c];
+                            &character == xmas_index.get(&i).unwrap()
+                        }) && two_diagonal.iter().enumerate().all(|(i, (r, c))| {
+                            let character = map[*r][*c];
+                            &character == xmas_index.get(&i).unwrap()
+                        }) {
                             result += 1
                         }
                     }
@@ -185,12 +168,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(9));
     }
 }
@@ -1,106 +1,103 @@
-use std::collections::HashSet;
-
 advent_of_code::solution!(6);
 
+use advent_of_code::{bitset::GridSet, cycle::brent, tile::Tile, visualizer::Visualizer};
+
 #[derive(Clone)]
 struct Guard {
     position: (isize, isize),
     direction: Direction,
-    visited: HashSet<(usize, usize, Direction)>,
+    /// Every `(column, row)` the guard entered, layered by the direction they were facing.
+    visited: GridSet,
 }
 
 impl Guard {
-    fn next_move(&mut self, map: &mut Vec<Vec<Pieces>>, inbounds: &mut bool) -> bool {
-        if let Pieces::Obstacle = map[self.position.0 as usize][self.position.1 as usize] {
-            panic!("illegal position");
-        }
+    /// Move the guard one step, recording the cell they enter.
+    /// Returns `false` once they leave the map.
+    fn next_move(&mut self, map: &[Vec<Pieces>]) -> bool {
+        let Some((position, direction)) = step(map, (self.position, self.direction)) else {
+            return false;
+        };
+        self.position = position;
+        self.direction = direction;
+        self.visited
+            .insert((position.1 as usize, position.0 as usize, direction as usize));
+        true
+    }
 
-        let mut pos: (isize, isize) = (0, 0);
-        while !self.can_move_ahead(map, &mut pos, inbounds) {
-            self.turn_ninety_degrees();
-        }
-        self.position = (pos.0, pos.1);
-        if self.inbounds(map) {
-            if self.visited.contains(&(
-                self.position.0 as usize,
-                self.position.1 as usize,
-                self.direction,
-            )) {
-                return true;
+    /// The map with the guard and the cells they visited, for [`Visualizer`].
+    fn render(&self, map: &[Vec<Pieces>]) -> String {
+        let mut out = String::new();
+        for (r, row) in map.iter().enumerate() {
+            for (c, piece) in row.iter().enumerate() {
+                out.push(if (r as isize, c as isize) == self.position {
+                    self.direction.to_char()
+                } else if *piece == Pieces::Space && self.visited.contains_cell(c, r) {
+                    'X'
+                } else {
+                    piece.to_char()
+                });
             }
-            self.visited.insert((
-                self.position.0 as usize,
-                self.position.1 as usize,
-                self.direction,
-            ));
+            out.push('\n');
         }
-        false
+        out
     }
+}
 
-    fn turn_ninety_degrees(&mut self) {
-        self.direction = match self.direction {
+advent_of_code::tiles! {
+    enum Pieces {
+        Space = '.',
+        Obstacle = '#',
+    }
+}
+
+advent_of_code::tiles! {
+    enum Direction {
+        North = '^',
+        South = 'v',
+        West = '<',
+        East = '>',
+    }
+}
+
+impl Direction {
+    fn turn_ninety_degrees(self) -> Self {
+        match self {
             Direction::North => Direction::East,
             Direction::South => Direction::West,
             Direction::West => Direction::North,
             Direction::East => Direction::South,
-        };
-    }
-
-    fn can_move_ahead(
-        &self,
-        map: &Vec<Vec<Pieces>>,
-        next_pos: &mut (isize, isize),
-        inbounds: &mut bool,
-    ) -> bool {
-        let next_position = match self.direction {
-            Direction::North => (self.position.0 - 1, self.position.1),
-            Direction::South => (self.position.0 + 1, self.position.1),
-            Direction::West => (self.position.0, self.position.1 - 1),
-            Direction::East => (self.position.0, self.position.1 + 1),
-        };
-        *next_pos = next_position;
-        let is_in = Self::inbounds_for_position(map, next_position);
-        *inbounds = is_in;
-        if is_in {
-            Pieces::Obstacle != map[next_position.0 as usize][next_position.1 as usize]
-        } else {
-            true
         }
     }
 
-    fn inbounds(&self, map: &Vec<Vec<Pieces>>) -> bool {
-        Self::inbounds_for_position(map, self.position)
-    }
-
-    fn inbounds_for_position(map: &Vec<Vec<Pieces>>, position: (isize, isize)) -> bool {
-        ((0 as isize)..(map.len() as isize)).contains(&position.0)
-            && ((0 as isize)..(map[0].len() as isize)).contains(&position.1)
+    fn ahead(self, (row, column): (isize, isize)) -> (isize, isize) {
+        match self {
+            Direction::North => (row - 1, column),
+            Direction::South => (row + 1, column),
+            Direction::West => (row, column - 1),
+            Direction::East => (row, column + 1),
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Pieces {
-    Space,
-    Obstacle,
-}
-
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
+fn inbounds(map: &[Vec<Pieces>], (row, column): (isize, isize)) -> bool {
+    (0..(map.len() as isize)).contains(&row) && (0..(map[0].len() as isize)).contains(&column)
 }
 
-impl Direction {
-    fn new(c: char) -> Option<Self> {
-        match c {
-            '<' => Some(Direction::West),
-            '>' => Some(Direction::East),
-            '^' => Some(Direction::North),
-            'v' => Some(Direction::South),
-            _ => None,
+/// The guard's next position and direction, or `None` once they leave the map.
+/// They turn right in place until nothing blocks the step ahead.
+fn step(
+    map: &[Vec<Pieces>],
+    (position, mut direction): ((isize, isize), Direction),
+) -> Option<((isize, isize), Direction)> {
+    loop {
+        let ahead = direction.ahead(position);
+        if !inbounds(map, ahead) {
+            return None;
         }
+        if map[ahead.0 as usize][ahead.1 as usize] != Pieces::Obstacle {
+            return Some((ahead, direction));
+        }
+        direction = direction.turn_ninety_degrees();
     }
 }
 
@@ -113,15 +110,13 @@ fn build_map(input: &str) -> (Vec<Vec<Pieces>>, (isize, isize), Direction) {
         .map(|(r, line)| {
             line.chars()
                 .enumerate()
-                .map(|(c, ch)| match ch {
-                    '^' | '<' | '>' | 'v' => {
+                .map(|(c, ch)| match Direction::try_from(ch) {
+                    Ok(guard) => {
                         position = Some((r as isize, c as isize));
-                        direction = Direction::new(ch);
+                        direction = Some(guard);
                         Pieces::Space
                     }
-                    '.' => Pieces::Space,
-                    '#' => Pieces::Obstacle,
-                    _ => panic!("unknown object"),
+                    Err(_) => Pieces::try_from(ch).unwrap(),
                 })
                 .collect::<Vec<_>>()
         })
@@ -132,42 +127,32 @@ fn build_map(input: &str) -> (Vec<Vec<Pieces>>, (isize, isize), Direction) {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (mut map, position, direction) = build_map(input);
+    let (map, position, direction) = build_map(input);
 
     let mut guard = Guard {
         position,
         direction,
-        visited: HashSet::new(),
+        visited: GridSet::with_layers(map[0].len(), map.len(), Direction::ALL.len()),
     };
 
-    // We know the guard is initially inbounds
-    let mut inbounds = true;
-
-    // Use the mutable variable to track inbounds
-    while inbounds {
-        guard.next_move(&mut map, &mut inbounds);
+    let mut viz = Visualizer::new();
+    while guard.next_move(&map) {
+        let (row, column) = guard.position;
+        viz.frame_around((column as usize, row as usize), || guard.render(&map));
     }
 
-    Some(
-        // The guard's visited property tracks each unique (row, column, direction) set,
-        // for reasons we'll see in part 2.
-        // So build a new set of visited based on (row, column) pairs only
-        guard
-            .visited
-            .iter()
-            .map(|(x, y, _)| (x, y))
-            .collect::<HashSet<_>>()
-            .len() as u32,
-    )
+    // The guard's visited property tracks each unique (column, row, direction) set,
+    // for reasons we'll see in part 2. Count the cells visited in any direction.
+    Some(guard.visited.count_cells() as u32)
 }
 
 /// We will need the direction later, but for now, we just need to get
 /// every position that the guard can reach.
 ///
-/// We use the direction to track if we are in a loop. We can visit the
-/// same location multiple times, but if we are facing different directions
-/// it doesn't mean much. If we come in at the same direction though, we
-/// know this is a loop, which is why we track direction.
+/// The guard is in a loop if their (position, direction) state repeats. We can
+/// visit the same location multiple times, but if we are facing different
+/// directions it doesn't mean much. Brent's cycle detection finds a repeated
+/// state without remembering every state along the way.
 ///
 /// Finally, while a naive implementation of this problem solves by brute
 /// forcing every possible space, we can observe that the only valid answers
@@ -179,21 +164,14 @@ pub fn part_two(input: &str) -> Option<u32> {
     let guard = Guard {
         position,
         direction,
-        visited: HashSet::new(),
+        visited: GridSet::with_layers(map[0].len(), map.len(), Direction::ALL.len()),
     };
 
     let mut guard_copy = guard.clone();
 
-    let mut inbounds = true;
-    while inbounds {
-        guard_copy.next_move(&mut map, &mut inbounds);
-    }
+    while guard_copy.next_move(&map) {}
 
-    let possible_paths = guard_copy
-        .visited
-        .iter()
-        .map(|(x, y, _)| (x, y))
-        .collect::<HashSet<_>>();
+    let possible_paths = guard_copy.visited;
 
     let mut result = 0;
 
@@ -201,20 +179,15 @@ pub fn part_two(input: &str) -> Option<u32> {
         for j in 0..map[i].len() {
             let is_empty_space = map[i][j] == Pieces::Space;
             let is_guards_spot = guard.position.0 as usize == i && guard.position.1 as usize == j;
-            let is_reachable = possible_paths.contains(&(&i, &j));
+            let is_reachable = possible_paths.contains_cell(j, i);
             if is_empty_space && !is_guards_spot && is_reachable {
-                let mut local_guard = guard.clone();
-                let mut inbounds = true;
-                let mut deep_copy: Vec<Vec<Pieces>> = map.iter().map(|v| (*v).clone()).collect();
-                deep_copy[i][j] = Pieces::Obstacle;
-
-                while inbounds {
-                    let loops = local_guard.next_move(&mut deep_copy, &mut inbounds);
-                    if loops {
-                        result += 1;
-                        break;
-                    }
+                // block the cell in place and clear it again, instead of copying the map.
+                map[i][j] = Pieces::Obstacle;
+                let start = (guard.position, guard.direction);
+                if brent(start, |&state| step(&map, state)).is_some() {
+                    result += 1;
                 }
+                map[i][j] = Pieces::Space;
             }
         }
     }
@@ -229,12 +202,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(6));
     }
 }
@@ -1,82 +1,82 @@
 advent_of_code::solution!(7);
 
-fn generate_sequences(
-    numbers: &[u64],
-    index: usize,
-    running: u64,
-    target: u64,
-    concat_mode: bool,
-) -> Vec<u64> {
-    if index == numbers.len() {
-        return vec![running];
-    }
-    if running > target {
-        return vec![u64::MAX]; // Exceeding target
-    }
+use advent_of_code::{combinatorics::CartesianPower, math::concat};
 
-    let next_number = numbers[index];
-    let mut results = Vec::new();
+#[derive(Clone, Copy, Debug)]
+enum Operator {
+    Add,
+    Multiply,
+    Concat,
+}
 
-    // Proceed with addition and multiplication, and concatenation if in concat_mode
-    results.extend(generate_sequences(
-        numbers,
-        index + 1,
-        running + next_number,
-        target,
-        concat_mode,
-    ));
+impl Operator {
+    /// The result, or `None` if it does not fit into a `u64`. `a` is `None` if it did not fit
+    /// either, which only multiplying by 0 recovers from.
+    fn apply(self, a: Option<u64>, b: u64) -> Option<u64> {
+        match (self, a) {
+            (Operator::Multiply, _) if b == 0 => Some(0),
+            (_, None) => None,
+            (Operator::Add, Some(a)) => a.checked_add(b),
+            (Operator::Multiply, Some(a)) => a.checked_mul(b),
+            (Operator::Concat, Some(a)) => concat(a, b),
+        }
+    }
+}
 
-    results.extend(generate_sequences(
-        numbers,
-        index + 1,
-        running * next_number,
-        target,
-        concat_mode,
-    ));
+/// Whether placing `operators` between `numbers` and evaluating left to right can give `target`.
+///
+/// The operator sequences are enumerated like an odometer, so only the results after the operators
+/// that changed are computed again. Only multiplying by 0 makes a result smaller, so once a prefix
+/// exceeds the target and no 0 follows, every sequence starting with it is skipped.
+fn is_solvable(target: u64, numbers: &[u64], operators: &[Operator]) -> bool {
+    let Some((&first, rest)) = numbers.split_first() else {
+        return false;
+    };
+    // prefixes up to the last 0 may still come back down to the target.
+    let last_zero = rest.iter().rposition(|&number| number == 0);
+    // values[i] is the result after the first i operators.
+    let mut values = vec![Some(first); numbers.len()];
+    let mut choices = CartesianPower::new(operators.len(), rest.len());
 
-    if concat_mode {
-        let num_digits = (next_number as f64).log(10.0).floor() as u64 + 1;
-        let concat_number = running * 10u64.pow(num_digits as u32) + next_number;
-        results.extend(generate_sequences(
-            numbers,
-            index + 1,
-            concat_number,
-            target,
-            concat_mode,
-        ));
+    while choices.next_indices().is_some() {
+        let exceeded = (choices.changed_from()..rest.len()).find(|&i| {
+            let operator = operators[choices.indices()[i]];
+            values[i + 1] = operator.apply(values[i], rest[i]);
+            values[i + 1].is_none_or(|value| value > target) && last_zero.is_none_or(|z| z <= i)
+        });
+        match exceeded {
+            Some(i) => choices.skip_prefix(i),
+            None if values[rest.len()] == Some(target) => return true,
+            None => {}
+        }
     }
-
-    results
+    false
 }
 
-fn process_line(line: &str, concat_mode: bool) -> Option<u64> {
+fn process_line(line: &str, operators: &[Operator]) -> Option<u64> {
     let (left, right) = line.split_once(":").unwrap();
     let pieces = right
-        .trim()
         .split_whitespace()
         .map(|num| num.parse::<u64>().unwrap())
         .collect::<Vec<_>>();
     let target = left.parse::<u64>().unwrap();
 
-    let targets = generate_sequences(&pieces, 0, 0, target, concat_mode);
-    if targets.iter().any(|&val| val == target) {
-        Some(target)
-    } else {
-        None
-    }
+    is_solvable(target, &pieces, operators).then_some(target)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
+    let operators = [Operator::Add, Operator::Multiply];
     input
         .lines()
-        .filter_map(|line| process_line(line, false))
+        .filter_map(|line| process_line(line, &operators))
         .reduce(|acc, v| acc + v)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
+    let operators = [Operator::Add, Operator::Multiply, Operator::Concat];
     input
         .lines()
-        .filter_map(|line| process_line(line, true))
+        .filter_map(|line| process_line(line, &operators))
         .reduce(|acc, v| acc + v)
 }
 
@@ -87,12 +87,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(11387));
+    }
+
+    #[test]
+    fn test_zero_operand() {
+        // the prefix exceeds the target, or does not even fit, before the 0 brings it back.
+        let operators = [Operator::Add, Operator::Multiply];
+        assert!(is_solvable(10, &[20, 0, 10], &operators));
+        assert!(is_solvable(7, &[u64::MAX, 2, 0, 7], &operators));
+        assert!(!is_solvable(10, &[20, 1, 10], &operators));
     }
 }
@@ -1,7 +1,7 @@
-use std::collections::HashSet;
-
 advent_of_code::solution!(10);
 
+use advent_of_code::bitset::GridSet;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Point {
     x: u32,
@@ -57,8 +57,8 @@ impl Map {
                 if let Some(height) = height {
                     if *height == 0 {
                         trailheads.push(Point {
-                            x: r.clone() as u32,
-                            y: c.clone() as u32,
+                            x: r as u32,
+                            y: c as u32,
                         });
                     }
                 }
@@ -71,27 +71,37 @@ impl Map {
         self.0
             .get(at.x as usize)
             .and_then(|row| row.get(at.y as usize))
-            .and_then(|point| point.clone())
+            .and_then(|point| *point)
     }
 }
 
 /// A hiker is a person who follows the perfect hike strategy and explores
 /// the map. It tracks score and rating, as well as remembers the peaks it
 /// has reached. The implementation is identical for both part 1 and part 2.
-#[derive(Clone)]
 struct Hiker {
     score: u32,
     rating: u32,
-    peaks_reached: HashSet<Point>,
+    /// The `(column, row)` of every peak reached from the current trailhead.
+    peaks_reached: GridSet,
 }
 
 impl Hiker {
-    fn new() -> Self {
+    fn new(map: &Map) -> Self {
         Self {
             score: 0,
             rating: 0,
-            peaks_reached: HashSet::new(),
+            peaks_reached: GridSet::new(map.0[0].len(), map.0.len()),
+        }
+    }
+
+    /// Sums score and rating over all trailheads, reusing the peaks of one for the next.
+    fn hike_all(map: &Map) -> Self {
+        let mut hiker = Self::new(map);
+        for trailhead in map.trailheads() {
+            hiker.peaks_reached.clear();
+            hiker.hike(map, &trailhead);
         }
+        hiker
     }
 
     fn hike(&mut self, map: &Map, point: &Point) {
@@ -99,17 +109,17 @@ impl Hiker {
         if height == 9 {
             self.rating += 1;
 
-            if !self.peaks_reached.contains(point) {
-                self.peaks_reached.insert(point.clone());
+            if self
+                .peaks_reached
+                .insert((point.y as usize, point.x as usize))
+            {
                 self.score += 1;
                 return;
             }
         }
         for neighbor in map.neighbors(point).iter() {
-            if let Some(neighbor_height) = map.get_height(&neighbor) {
-                if neighbor_height == height + 1 {
-                    self.hike(map, &neighbor);
-                }
+            if map.get_height(neighbor) == Some(height + 1) {
+                self.hike(map, neighbor);
             }
         }
     }
@@ -117,30 +127,12 @@ impl Hiker {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let map = Map::new(input);
-    let trailheads = map.trailheads();
-
-    let mut hikers = vec![];
-    for trailhead in trailheads {
-        let mut hiker = Hiker::new();
-        hiker.hike(&map, &trailhead);
-        hikers.push(hiker.clone());
-    }
-
-    hikers.iter().map(|h| h.score).reduce(|acc, v| acc + v)
+    Some(Hiker::hike_all(&map).score)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let map = Map::new(input);
-    let trailheads = map.trailheads();
-
-    let mut hikers = vec![];
-    for trailhead in trailheads {
-        let mut hiker = Hiker::new();
-        hiker.hike(&map, &trailhead);
-        hikers.push(hiker.clone());
-    }
-
-    hikers.iter().map(|h| h.rating).reduce(|acc, v| acc + v)
+    Some(Hiker::hike_all(&map).rating)
 }
 
 #[cfg(test)]
@@ -150,12 +142,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(81));
     }
 }
@@ -1,36 +1,37 @@
-use cached::proc_macro::cached;
-
 advent_of_code::solution!(11);
 
-#[cached]
-fn blink(n: u64, b: u64, target: u64) -> u64 {
-    if b == target {
-        return 1;
+use advent_of_code::counter::Counter;
+use advent_of_code::math::split_half;
+
+// The stones a single stone turns into with one blink.
+fn blink(stone: u64) -> [Option<u64>; 2] {
+    if stone == 0 {
+        return [Some(1), None];
     }
-    if n == 0 {
-        return blink(1, b + 1, target);
+    match split_half(stone) {
+        Some((left, right)) => [Some(left), Some(right)],
+        None => [Some(stone * 2024), None],
     }
-    let mut left = n.to_string();
-    if left.len() % 2 == 0 {
-        let right = left.split_off(left.len() / 2);
-        return blink(left.parse().unwrap(), b + 1, target)
-            + blink(right.parse().unwrap(), b + 1, target);
+}
+
+// Stones with the same number evolve the same way, so only count them.
+fn count_stones(input: &str, blinks: usize) -> u64 {
+    let mut stones = input
+        .split_whitespace()
+        .map(|m| m.parse::<u64>().unwrap())
+        .collect::<Counter<_>>();
+    for _ in 0..blinks {
+        stones = stones.flat_map_counts(|&stone| blink(stone).into_iter().flatten());
     }
-    blink(n * 2024, b + 1, target)
+    stones.total()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let stones = input.split(" ").map(|m| m.parse::<u64>().unwrap());
-    stones
-        .map(|stone| blink(stone, 0, 25))
-        .reduce(|acc, v| acc + v)
+    Some(count_stones(input, 25))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let stones = input.split(" ").map(|m| m.parse::<u64>().unwrap());
-    stones
-        .map(|stone| blink(stone, 0, 75))
-        .reduce(|acc, v| acc + v)
+    Some(count_stones(input, 75))
 }
 
 #[cfg(test)]
@@ -40,12 +41,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(65601038650482));
     }
 }
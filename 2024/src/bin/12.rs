@@ -1,176 +1,122 @@
-use std::{
-    collections::{HashMap, HashSet},
-    hash::{Hash, Hasher},
-};
-
 advent_of_code::solution!(12);
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-struct Point(i32, i32);
+use advent_of_code::{bitset::GridSet, disjoint_set::DisjointSet, grid::Grid};
 
-impl Hash for Point {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
-        self.1.hash(state);
-    }
+/// The `(dx, dy)` steps to the four neighbors of a plot.
+const DIR: [(isize, isize); 4] = [(0, 1), (1, 0), (-1, 0), (0, -1)];
+
+/// Moves `cell` by `(dx, dy)`. Moving below zero wraps to a cell outside of any set.
+fn offset((x, y): (usize, usize), (dx, dy): (isize, isize)) -> (usize, usize) {
+    (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
 }
 
-const DIR: [(i32, i32); 4] = [(1, 0), (0, 1), (0, -1), (-1, 0)];
+/// The plots of one region at a time, bordered so the neighbors of every plot are in bounds.
+/// Both sets are reused for the next region. The sides are removed again cell by cell,
+/// since clearing and scanning all of the map for every direction of every region costs more
+/// than the many small regions themselves.
+struct Region {
+    plots: GridSet,
+    sides: GridSet,
+}
 
-impl Point {
-    fn perimeter(points: &HashSet<&Point>) -> u32 {
-        let mut perimeter = 0;
-        for point in points {
-            let mut local_perimeter = 4;
-            if points.contains(&Point(point.0 + 1, point.1)) {
-                local_perimeter -= 1;
-            }
-            if points.contains(&Point(point.0 - 1, point.1)) {
-                local_perimeter -= 1;
-            }
-            if points.contains(&Point(point.0, point.1 + 1)) {
-                local_perimeter -= 1;
-            }
-            if points.contains(&Point(point.0, point.1 - 1)) {
-                local_perimeter -= 1;
-            }
-            perimeter += local_perimeter
+impl Region {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            plots: GridSet::new(width + 2, height + 2),
+            sides: GridSet::new(width + 2, height + 2),
         }
-        perimeter
     }
 
-    fn sides(points: &HashSet<&Point>) -> u32 {
-        let mut side_count: u32 = 0;
-        for dir in DIR {
-            let mut sides = HashSet::new();
-            for point in points {
-                let tmp = Point(point.0 + dir.0, point.1 + dir.1);
-                if !points.contains(&tmp) {
-                    sides.insert(tmp);
-                }
-            }
-            let mut remove = HashSet::new();
-            for side in sides.clone() {
-                let mut tmp = Point(side.0 + dir.1, side.1 + dir.0);
-                while sides.contains(&tmp) {
-                    remove.insert(tmp.clone());
-                    tmp = Point(tmp.0 + dir.1, tmp.1 + dir.0);
-                }
-            }
-            side_count += (sides.len() - remove.len()) as u32
+    /// Fill the region with `cells`, which are shifted by one for the border.
+    fn fill(&mut self, cells: &[(usize, usize)]) {
+        self.plots.clear();
+        for &cell in cells {
+            self.plots.insert(cell);
         }
-        side_count
     }
-}
-
-#[derive(Debug)]
-struct UnionFind(HashMap<Point, Point>);
 
-impl UnionFind {
-    fn new() -> Self {
-        Self(HashMap::new())
+    fn perimeter(&self, cells: &[(usize, usize)]) -> u32 {
+        cells
+            .iter()
+            .flat_map(|&cell| DIR.map(|dir| offset(cell, dir)))
+            .filter(|neighbor| !self.plots.contains(*neighbor))
+            .count() as u32
     }
 
-    fn insert(&mut self, x: Point) {
-        self.0.entry(x.clone()).or_insert(x.clone());
-    }
-
-    fn find(&mut self, x: &Point) -> Point {
-        if let Some(parent) = self.0.get(x).cloned() {
-            if parent != *x {
-                // Path compression
-                let root = self.find(&parent);
-                self.0.insert(x.clone(), root.clone());
-                root
-            } else {
-                parent
+    fn sides(&mut self, cells: &[(usize, usize)]) -> u32 {
+        let mut side_count = 0;
+        for dir in DIR {
+            let edges = cells
+                .iter()
+                .map(|&cell| offset(cell, dir))
+                .filter(|&neighbor| !self.plots.contains(neighbor));
+            for edge in edges.clone() {
+                self.sides.insert(edge);
+            }
+            // Each side is a run of cells across `dir`, counted at the cell it starts with.
+            let back = (-dir.1, -dir.0);
+            side_count += edges
+                .clone()
+                .filter(|&edge| !self.sides.contains(offset(edge, back)))
+                .count() as u32;
+            for edge in edges {
+                self.sides.remove(edge);
             }
-        } else {
-            // If there's no parent, the point is its own parent
-            self.0.insert(x.clone(), x.clone());
-            x.clone()
         }
+        side_count
     }
+}
 
-    fn union(&mut self, x: &Point, y: &Point) {
-        let rx = self.find(x);
-        let ry = self.find(y);
-        if rx != ry {
-            self.0.insert(ry, rx);
+/// The map and the plots of every region, as `(x, y)` cells shifted by one for the border of a [`Region`].
+fn regions(input: &str) -> (Grid<char>, Vec<Vec<(usize, usize)>>) {
+    let map = Grid::parse(input, |plant| plant).unwrap();
+    let mut plots = DisjointSet::new(map.len());
+    for ((x, y), plant) in map.iter() {
+        let (x, y) = (x as isize, y as isize);
+        for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+            if map.get(nx, ny) == Some(plant) {
+                plots.union(map.index_of(x, y).unwrap(), map.index_of(nx, ny).unwrap());
+            }
         }
     }
-}
 
-fn process(input: &str) -> (UnionFind, HashMap<Point, Vec<Point>>) {
-    let mut union_find = UnionFind::new();
-    let mut points = vec![];
-    let map: Vec<Vec<char>> = input
-        .lines()
-        .enumerate()
-        .map(|(r, row)| {
-            row.chars()
-                .enumerate()
-                .map(|(c, val)| {
-                    let p = Point(r as i32, c as i32);
-                    points.push(p.clone());
-                    union_find.insert(p.clone());
-                    val
-                })
+    let regions = plots
+        .components()
+        .into_iter()
+        .map(|region| {
+            region
+                .into_iter()
+                .map(|i| offset(map.position_of(i), (1, 1)))
                 .collect()
         })
         .collect();
-
-    for (r, row) in input.lines().enumerate() {
-        for (c, _) in row.chars().enumerate() {
-            if r >= 1 {
-                if map[r][c] == map[r - 1][c] {
-                    let p1 = Point(r as i32, c as i32);
-                    let p2 = Point((r - 1) as i32, c as i32);
-                    union_find.union(&p1, &p2);
-                }
-            }
-            if c >= 1 {
-                if map[r][c] == map[r][c - 1] {
-                    let p1 = Point(r as i32, c as i32);
-                    let p2 = Point(r as i32, (c - 1) as i32);
-                    union_find.union(&p1, &p2);
-                }
-            }
-        }
-    }
-
-    let mut reverse_map = HashMap::<Point, Vec<Point>>::new();
-    for point in points {
-        let parent = union_find.find(&point);
-        reverse_map.entry(parent).or_insert(Vec::new()).push(point);
-    }
-    (union_find, reverse_map)
+    (map, regions)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, reverse_map) = process(input);
+    let (map, regions) = regions(input);
+    let mut region = Region::new(map.width(), map.height());
 
-    reverse_map
+    regions
         .iter()
-        .map(|(_, points)| {
-            let area = points.len() as u32;
-            let item_set = points.iter().collect::<HashSet<_>>();
-            let perimeter = Point::perimeter(&item_set);
-            area * perimeter
+        .map(|cells| {
+            let area = cells.len() as u32;
+            region.fill(cells);
+            area * region.perimeter(cells)
         })
         .reduce(|acc, v| acc + v)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, reverse_map) = process(input);
+    let (map, regions) = regions(input);
+    let mut region = Region::new(map.width(), map.height());
 
-    reverse_map
+    regions
         .iter()
-        .map(|(_, points)| {
-            let area = points.len() as u32;
-            let item_set = points.iter().collect::<HashSet<_>>();
-            let sides = Point::sides(&item_set);
-            area * sides
+        .map(|cells| {
+            let area = cells.len() as u32;
+            region.fill(cells);
+            area * region.sides(cells)
         })
         .reduce(|acc, v| acc + v)
 }
@@ -182,12 +128,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(140));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(80));
     }
 }
@@ -1,5 +1,18 @@
 advent_of_code::solution!(14);
 
+use advent_of_code::{
+    cycle::brent,
+    image::{Image, BLACK, WHITE},
+    math::lcm,
+    point::Point,
+    sparse_grid::SparseGrid,
+    template::debug,
+    visualizer::Visualizer,
+};
+
+/// The width and height of the room the robots move in.
+const SIZE: (i32, i32) = (101, 103);
+
 #[derive(Debug)]
 struct Robot {
     position: (i32, i32),
@@ -22,34 +35,68 @@ impl Robot {
             .collect()
     }
 
-    fn make_move(&mut self) {
-        self.position.0 = (self.position.0 + self.velocity.0).rem_euclid(101);
-        self.position.1 = (self.position.1 + self.velocity.1).rem_euclid(103);
+    fn make_move(&mut self, (width, height): (i32, i32)) {
+        self.position.0 = (self.position.0 + self.velocity.0).rem_euclid(width);
+        self.position.1 = (self.position.1 + self.velocity.1).rem_euclid(height);
     }
 
-    fn to_map(robots: &Vec<Robot>) -> Vec<Vec<usize>> {
-        let mut map: Vec<Vec<usize>> = vec![];
-        for _ in 0..103 {
-            map.push(vec![0; 103]);
-        }
-        for robot in robots.iter() {
-            map[robot.position.0 as usize][robot.position.1 as usize] = 1;
-        }
-        map
+    fn point(&self) -> Point<i64> {
+        Point::new(self.position.0 as i64, self.position.1 as i64)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// The number of seconds until the robots are back where they started.
+fn period(robots: &[Robot], (width, height): (i32, i32)) -> usize {
+    // the robots move along the columns and the rows independently, and each axis
+    // repeats long before the whole room does, so find the cycle of each on its own.
+    let axis_period = |axis: fn(&(i32, i32)) -> i32, size: i32| {
+        let start = robots.iter().map(|robot| axis(&robot.position)).collect();
+        let cycle = brent(start, |positions: &Vec<i32>| {
+            let moved = positions.iter().zip(robots);
+            Some(
+                moved
+                    .map(|(p, robot)| (p + axis(&robot.velocity)).rem_euclid(size))
+                    .collect(),
+            )
+        })
+        .unwrap();
+        // every move can be undone, so the cycle starts right away.
+        cycle.length as i64
+    };
+    let columns = axis_period(|&(x, _)| x, width);
+    let rows = axis_period(|&(_, y)| y, height);
+    lcm(columns, rows).unwrap() as usize
+}
+
+/// Whether ten robots stand in a column without gaps, like the frame around the tree.
+fn has_line(grid: &SparseGrid<usize>) -> bool {
+    let Some(rows) = grid.y_range() else {
+        return false;
+    };
+    grid.column_counts()
+        .filter(|&(_, count)| count >= 10)
+        .any(|(x, _)| {
+            let mut run = 0;
+            rows.clone().any(|y| {
+                run = if grid.contains(Point::new(x, y)) {
+                    run + 1
+                } else {
+                    0
+                };
+                run == 10
+            })
+        })
+}
+
+/// The product of the robot counts in the four quadrants after 100 seconds.
+fn safety_factor(input: &str, (width, height): (i32, i32)) -> u32 {
     let mut robots = Robot::parse(input);
     for _ in 0..100 {
         for robot in robots.iter_mut() {
-            robot.make_move();
+            robot.make_move((width, height));
         }
     }
 
-    // Grid dimensions
-    let width = 101;
-    let height = 103;
     let center_x = width / 2;
     let center_y = height / 2;
 
@@ -62,41 +109,53 @@ pub fn part_one(input: &str) -> Option<u32> {
     for robot in robots {
         let (x, y) = robot.position;
 
-        if x == center_x as i32 || y == center_y as i32 {
+        if x == center_x || y == center_y {
             // Skip robots in the middle row or column
             continue;
         }
 
-        if x < center_x as i32 && y < center_y as i32 {
+        if x < center_x && y < center_y {
             top_left += 1;
-        } else if x > center_x as i32 && y < center_y as i32 {
+        } else if x > center_x && y < center_y {
             top_right += 1;
-        } else if x < center_x as i32 && y > center_y as i32 {
+        } else if x < center_x && y > center_y {
             bottom_left += 1;
-        } else if x > center_x as i32 && y > center_y as i32 {
+        } else if x > center_x && y > center_y {
             bottom_right += 1;
         }
     }
 
-    // Calculate safety factor as product of counts in all quadrants
-    let safety_factor = top_left * top_right * bottom_left * bottom_right;
+    top_left * top_right * bottom_left * bottom_right
+}
 
-    Some(safety_factor)
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(safety_factor(input, SIZE))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut robots = Robot::parse(input);
-    for num_seconds in 1.. {
+    let mut grid = SparseGrid::new();
+    for robot in robots.iter() {
+        grid.add(robot.point());
+    }
+
+    // if the tree shows up at all, it shows up before the robots repeat their positions.
+    let period = period(&robots, SIZE) as u32;
+    let mut viz = Visualizer::new();
+    for num_seconds in 1..period {
         for robot in robots.iter_mut() {
-            robot.make_move();
+            grid.subtract(robot.point());
+            robot.make_move(SIZE);
+            grid.add(robot.point());
         }
-        let map = Robot::to_map(&robots);
-        for line in map.iter() {
-            for window in line.windows(10) {
-                if window == &vec![1; 10] {
-                    return Some(num_seconds);
-                }
-            }
+        viz.frame(|| grid.render(|cell| if cell.is_some() { '#' } else { ' ' }));
+        if has_line(&grid) {
+            // the heuristic only finds a line, so look at the tree with `--debug-frames`.
+            debug::frame(|| {
+                Image::from_sparse_grid(&grid, |cell| if cell.is_some() { WHITE } else { BLACK })
+                    .scaled(4)
+            });
+            return Some(num_seconds);
         }
     }
     None
@@ -108,13 +167,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        // the example room is 11 tiles wide and 7 tall.
+        let result = safety_factor(
+            &advent_of_code::template::read_file("examples", DAY),
+            (11, 7),
+        );
+        assert_eq!(result, 12);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        // the example has no tree, but its robots repeat like the real ones, every 11 * 7 seconds.
+        let robots = Robot::parse(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(period(&robots, (11, 7)), 77);
     }
 }
@@ -1,64 +1,32 @@
 advent_of_code::solution!(15);
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Elem {
-    Empty,
-    Box,
-    Wall,
-    Robot,
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum WideElem {
-    Empty,
-    LeftBox,
-    RightBox,
-    Wall,
-    Robot,
-}
-
-impl From<char> for Elem {
-    fn from(value: char) -> Self {
-        match value {
-            '#' => Self::Wall,
-            '@' => Self::Robot,
-            '.' => Self::Empty,
-            'O' => Self::Box,
-            _ => panic!("unrecognized element"),
-        }
+use advent_of_code::{tile::Tile, visualizer::Visualizer};
+
+advent_of_code::tiles! {
+    enum Elem {
+        Empty = '.',
+        Box = 'O',
+        Wall = '#',
+        Robot = '@',
     }
 }
 
-impl From<char> for WideElem {
-    fn from(value: char) -> Self {
-        match value {
-            '#' => Self::Wall,
-            '@' => Self::Robot,
-            '.' => Self::Empty,
-            '[' => Self::LeftBox,
-            ']' => Self::RightBox,
-            _ => panic!("unrecognized element"),
-        }
+advent_of_code::tiles! {
+    enum WideElem {
+        Empty = '.',
+        LeftBox = '[',
+        RightBox = ']',
+        Wall = '#',
+        Robot = '@',
     }
 }
 
-#[derive(Debug)]
-enum Instruction {
-    Up,
-    Left,
-    Right,
-    Down,
-}
-
-impl From<char> for Instruction {
-    fn from(value: char) -> Self {
-        match value {
-            '<' => Self::Left,
-            '>' => Self::Right,
-            'v' => Self::Down,
-            '^' => Self::Up,
-            _ => panic!("unrecognized instruction"),
-        }
+advent_of_code::tiles! {
+    enum Instruction {
+        Up = '^',
+        Left = '<',
+        Right = '>',
+        Down = 'v',
     }
 }
 
@@ -81,13 +49,13 @@ fn process(input: &str) -> (Warehouse, (isize, isize), Vec<Instruction>) {
     let (map, instructions) = input.split_once("\n\n").unwrap();
     let warehouse: Warehouse = map
         .lines()
-        .map(|row| row.chars().map(|ch| ch.into()).collect::<Vec<Elem>>())
+        .map(|row| row.chars().map(|ch| Elem::try_from(ch).unwrap()).collect())
         .collect();
     let processed_instructions: Vec<Instruction> = instructions
         .trim()
         .chars()
         .filter(|ch| !ch.is_whitespace())
-        .map(|ch| ch.into())
+        .map(|ch| Instruction::try_from(ch).unwrap())
         .collect();
 
     let mut robot_pos = None;
@@ -108,13 +76,17 @@ fn process_wide(
 ) -> (WideWarehouse, (isize, isize), Vec<Instruction>) {
     let warehouse: WideWarehouse = map
         .lines()
-        .map(|row| row.chars().map(|ch| ch.into()).collect::<Vec<WideElem>>())
+        .map(|row| {
+            row.chars()
+                .map(|ch| WideElem::try_from(ch).unwrap())
+                .collect()
+        })
         .collect();
     let processed_instructions: Vec<Instruction> = instructions
         .trim()
         .chars()
         .filter(|ch| !ch.is_whitespace())
-        .map(|ch| ch.into())
+        .map(|ch| Instruction::try_from(ch).unwrap())
         .collect();
 
     let mut robot_pos = None;
@@ -198,9 +170,7 @@ fn move_wide_robot(from: &mut (isize, isize), direction: &Instruction, on: &mut
             let mut prev = WideElem::Empty;
             let mut p = (from.0 + step.0, from.1 + step.1);
             for _ in 0..size {
-                let curr = on[p.0 as usize][p.1 as usize];
-                on[p.0 as usize][p.1 as usize] = prev;
-                prev = curr;
+                std::mem::swap(&mut on[p.0 as usize][p.1 as usize], &mut prev);
                 p = (p.0 + step.0, p.1 + step.1);
             }
             // Move robot
@@ -302,6 +272,14 @@ fn move_wide_robot(from: &mut (isize, isize), direction: &Instruction, on: &mut
     }
 }
 
+/// The warehouse as in the puzzle, for [`Visualizer`].
+fn render<T: Tile>(warehouse: &[Vec<T>]) -> String {
+    warehouse
+        .iter()
+        .flat_map(|row| row.iter().map(|elem| elem.to_char()).chain(['\n']))
+        .collect()
+}
+
 fn gps_coordinate(point: (usize, usize)) -> u32 {
     (point.0 as u32) * 100 + point.1 as u32
 }
@@ -329,8 +307,11 @@ fn widen_map(input: &str) -> String {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut warehouse, mut robot_pos, instructions) = process(input);
+    let mut viz = Visualizer::new();
     for instruction in instructions.iter() {
         move_robot(&mut robot_pos, instruction, &mut warehouse);
+        let (row, column) = robot_pos;
+        viz.frame_around((column as usize, row as usize), || render(&warehouse));
     }
     warehouse
         .iter()
@@ -355,8 +336,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     let (map, instructions) = input.split_once("\n\n").unwrap();
     let wide_map = widen_map(map);
     let (mut warehouse, mut robot_pos, instructions) = process_wide(&wide_map, instructions);
+    let mut viz = Visualizer::new();
     for instruction in instructions.iter() {
         move_wide_robot(&mut robot_pos, instruction, &mut warehouse);
+        let (row, column) = robot_pos;
+        viz.frame_around((column as usize, row as usize), || render(&warehouse));
     }
     warehouse
         .iter()
@@ -384,12 +368,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_15_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(9021));
     }
 }
@@ -0,0 +1,375 @@
+//! Dense sets of small integers, backed by one bit per possible member.
+//!
+//! [`GridSet`] maps the cells of a grid onto a [`BitSet`], optionally with extra layers per cell,
+//! e.g. one per [`Dir4`] to track the directions a cell was visited from. Both are much faster than a
+//! `HashSet` of coordinates for visited tracking and can be cleared in place to reuse their memory.
+
+use crate::{direction::Dir4, grid::Grid};
+
+const BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    /// An empty set for the values `0..capacity`.
+    pub fn new(capacity: usize) -> Self {
+        BitSet {
+            words: vec![0; capacity.div_ceil(BITS)],
+            capacity,
+        }
+    }
+
+    /// The values this set can hold are `0..capacity`.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Add `value`. Returns whether it was newly inserted. Panics if `value` is out of range.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = self.locate(value);
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        inserted
+    }
+
+    /// Remove `value`. Returns whether it was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= self.capacity {
+            return false;
+        }
+        let (word, mask) = self.locate(value);
+        let removed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        removed
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.capacity && {
+            let (word, mask) = self.locate(value);
+            self.words[word] & mask != 0
+        }
+    }
+
+    /// Remove all values, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of values in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// All values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * BITS + bit
+                })
+            })
+        })
+    }
+
+    /// Add all values of `other`, which must have the same capacity.
+    pub fn union_with(&mut self, other: &BitSet) {
+        assert_eq!(self.capacity, other.capacity, "capacities differ");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Keep only the values that are also in `other`, which must have the same capacity.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        assert_eq!(self.capacity, other.capacity, "capacities differ");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    fn locate(&self, value: usize) -> (usize, u64) {
+        assert!(
+            value < self.capacity,
+            "value {value} is out of range for a capacity of {}",
+            self.capacity
+        );
+        (value / BITS, 1 << (value % BITS))
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A member of a [`GridSet`]: an `(x, y)` cell and the layer of that cell.
+pub trait GridKey {
+    fn cell(&self) -> (usize, usize);
+    fn layer(&self) -> usize;
+}
+
+/// A cell in layer `0`.
+impl GridKey for (usize, usize) {
+    fn cell(&self) -> (usize, usize) {
+        *self
+    }
+
+    fn layer(&self) -> usize {
+        0
+    }
+}
+
+/// A cell `(x, y)` in the given layer.
+impl GridKey for (usize, usize, usize) {
+    fn cell(&self) -> (usize, usize) {
+        (self.0, self.1)
+    }
+
+    fn layer(&self) -> usize {
+        self.2
+    }
+}
+
+/// A cell in the layer of the direction, see [`GridSet::with_directions`].
+impl GridKey for ((usize, usize), Dir4) {
+    fn cell(&self) -> (usize, usize) {
+        self.0
+    }
+
+    fn layer(&self) -> usize {
+        self.1.index()
+    }
+}
+
+/// A set of `(x, y)` cells of a `width` × `height` grid, with `layers` independent members per cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GridSet {
+    width: usize,
+    height: usize,
+    layers: usize,
+    bits: BitSet,
+}
+
+impl GridSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_layers(width, height, 1)
+    }
+
+    pub fn with_layers(width: usize, height: usize, layers: usize) -> Self {
+        GridSet {
+            width,
+            height,
+            layers,
+            bits: BitSet::new(width * height * layers),
+        }
+    }
+
+    /// A set with one layer per [`Dir4`], keyed by `((x, y), Dir4)`.
+    pub fn with_directions(width: usize, height: usize) -> Self {
+        Self::with_layers(width, height, Dir4::COUNT)
+    }
+
+    /// An empty set of the same size as `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /// Add `key`. Returns whether it was newly inserted. Panics if it lies outside the grid.
+    pub fn insert(&mut self, key: impl GridKey) -> bool {
+        let index = self.index_of(&key).unwrap_or_else(|| {
+            panic!(
+                "cell {:?} in layer {} is outside of a {}x{}x{} grid set",
+                key.cell(),
+                key.layer(),
+                self.width,
+                self.height,
+                self.layers
+            )
+        });
+        self.bits.insert(index)
+    }
+
+    /// Remove `key`. Returns whether it was present.
+    pub fn remove(&mut self, key: impl GridKey) -> bool {
+        self.index_of(&key)
+            .is_some_and(|index| self.bits.remove(index))
+    }
+
+    pub fn contains(&self, key: impl GridKey) -> bool {
+        self.index_of(&key)
+            .is_some_and(|index| self.bits.contains(index))
+    }
+
+    /// Whether `(x, y)` is in the set in any layer.
+    pub fn contains_cell(&self, x: usize, y: usize) -> bool {
+        (0..self.layers).any(|layer| self.contains((x, y, layer)))
+    }
+
+    /// Remove all members, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The number of members, counting every layer.
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    /// The number of cells that are in the set in any layer.
+    pub fn count_cells(&self) -> usize {
+        self.cells().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// All members as `(x, y, layer)`, ordered by row, then column, then layer.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.bits.iter().map(|index| {
+            let (cell, layer) = (index / self.layers, index % self.layers);
+            (cell % self.width, cell / self.width, layer)
+        })
+    }
+
+    /// All cells that are in the set in any layer, ordered by row, then column.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut last = None;
+        self.iter().filter_map(move |(x, y, _)| {
+            let cell = Some((x, y));
+            (last != cell).then(|| {
+                last = cell;
+                (x, y)
+            })
+        })
+    }
+
+    /// Add all members of `other`, which must have the same dimensions.
+    pub fn union_with(&mut self, other: &GridSet) {
+        assert_eq!(
+            (self.width, self.height, self.layers),
+            (other.width, other.height, other.layers),
+            "dimensions differ"
+        );
+        self.bits.union_with(&other.bits);
+    }
+
+    fn index_of(&self, key: &impl GridKey) -> Option<usize> {
+        let ((x, y), layer) = (key.cell(), key.layer());
+        (x < self.width && y < self.height && layer < self.layers)
+            .then(|| (y * self.width + x) * self.layers + layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitSet, GridSet};
+    use crate::{direction::Dir4, grid::Grid};
+
+    #[test]
+    fn tracks_bits() {
+        let mut set = BitSet::new(130);
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert!(!set.insert(64));
+        assert!(set.contains(129));
+        assert!(!set.contains(1));
+        assert!(!set.contains(1000));
+        assert_eq!(set.count(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 129]);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert!(!set.remove(1000));
+        assert_eq!(set.count(), 2);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.capacity(), 130);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let mut a = BitSet::new(100);
+        let mut b = BitSet::new(100);
+        a.extend([1, 2, 3, 70]);
+        b.extend([3, 70, 99]);
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.iter().collect::<Vec<_>>(), vec![1, 2, 3, 70, 99]);
+        a.intersect_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![3, 70]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn rejects_values_out_of_range() {
+        BitSet::new(10).insert(10);
+    }
+
+    #[test]
+    fn tracks_grid_cells() {
+        let grid = Grid::parse("...\n...", |c| c).unwrap();
+        let mut set = GridSet::for_grid(&grid);
+        assert!(set.insert((2, 1)));
+        assert!(!set.insert((2, 1)));
+        assert!(set.contains((2, 1)));
+        assert!(!set.contains((1, 2)));
+        assert!(!set.contains((3, 0)));
+        set.insert((0, 1));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 1, 0), (2, 1, 0)]);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn tracks_directions() {
+        let mut set = GridSet::with_directions(4, 4);
+        assert!(set.insert(((1, 1), Dir4::Up)));
+        assert!(set.insert(((1, 1), Dir4::Left)));
+        assert!(!set.insert(((1, 1), Dir4::Up)));
+        assert!(set.insert(((3, 0), Dir4::Down)));
+        assert!(set.contains(((1, 1), Dir4::Left)));
+        assert!(!set.contains(((1, 1), Dir4::Right)));
+        assert!(set.contains_cell(1, 1));
+        assert!(!set.contains_cell(0, 0));
+
+        assert_eq!(set.count(), 3);
+        assert_eq!(set.count_cells(), 2);
+        assert_eq!(set.cells().collect::<Vec<_>>(), vec![(3, 0), (1, 1)]);
+        assert!(set.remove(((1, 1), Dir4::Up)));
+        assert!(set.contains((1, 1, Dir4::Left.index())));
+    }
+}
@@ -0,0 +1,325 @@
+//! Enumerating pairs, combinations, permutations and Cartesian powers without allocating per item.
+//!
+//! [`pairs`] is a plain iterator over references. The others work on one buffer of indices that is
+//! updated in place, so they hand out `&[usize]` from `next_indices` instead of implementing
+//! [`Iterator`]: map the indices onto your own slice, e.g. `operators[i]`, and loop with
+//! `while let Some(indices) = iter.next_indices()`, or read them again with `indices`.
+//! All of them enumerate in lexicographic order.
+
+/// Every unordered pair of distinct positions of `items`, `(items[i], items[j])` with `i < j`.
+pub fn pairs<T>(items: &[T]) -> Pairs<'_, T> {
+    Pairs { items, i: 0, j: 1 }
+}
+
+#[derive(Clone, Debug)]
+pub struct Pairs<'a, T> {
+    items: &'a [T],
+    i: usize,
+    j: usize,
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.j >= self.items.len() {
+            self.i += 1;
+            self.j = self.i + 1;
+            if self.j >= self.items.len() {
+                return None;
+            }
+        }
+        let pair = (&self.items[self.i], &self.items[self.j]);
+        self.j += 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.items.len();
+        // the rest of row `i`, then the complete rows after it.
+        let rest = if self.i + 1 >= n {
+            0
+        } else {
+            let rows = n - self.i - 1;
+            (n - self.j) + rows * (rows - 1) / 2
+        };
+        (rest, Some(rest))
+    }
+}
+
+impl<T> ExactSizeIterator for Pairs<'_, T> {}
+
+/* -------------------------------------------------------------------------- */
+
+/// The `k`-element subsets of `0..n`, each in ascending order.
+#[derive(Clone, Debug)]
+pub struct Combinations {
+    n: usize,
+    indices: Vec<usize>,
+    started: bool,
+}
+
+impl Combinations {
+    pub fn new(n: usize, k: usize) -> Self {
+        Combinations {
+            n,
+            indices: (0..k).collect(),
+            started: false,
+        }
+    }
+
+    /// The next combination, `None` after the last.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        let k = self.indices.len();
+        if !self.started {
+            self.started = true;
+            return (k <= self.n).then_some(&self.indices[..]);
+        }
+        // the rightmost index that can still move right, with room for the ones after it.
+        let i = (0..k).rev().find(|&i| self.indices[i] + k < self.n + i)?;
+        self.indices[i] += 1;
+        for j in i + 1..k {
+            self.indices[j] = self.indices[j - 1] + 1;
+        }
+        Some(&self.indices)
+    }
+
+    /// The current combination, the one last returned by `next_indices`.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every ordering of `0..n`.
+#[derive(Clone, Debug)]
+pub struct Permutations {
+    indices: Vec<usize>,
+    started: bool,
+}
+
+impl Permutations {
+    pub fn new(n: usize) -> Self {
+        Permutations {
+            indices: (0..n).collect(),
+            started: false,
+        }
+    }
+
+    /// The next permutation, `None` after the last.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if !self.started {
+            self.started = true;
+            return Some(&self.indices);
+        }
+        next_permutation(&mut self.indices).then_some(&self.indices[..])
+    }
+
+    /// The current permutation, the one last returned by `next_indices`.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+/// Rearrange `items` into the next greater permutation in lexicographic order. Returns `false` and
+/// leaves them sorted in ascending order if they already were the greatest one. Equal items are
+/// not told apart, so every distinct arrangement comes up once.
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // the rightmost item that is smaller than the one after it.
+    let Some(i) = (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) else {
+        items.reverse();
+        return false;
+    };
+    let pivot = i - 1;
+    // the rightmost item after the pivot that is larger than it, the smallest such one.
+    let j = (i..items.len())
+        .rev()
+        .find(|&j| items[j] > items[pivot])
+        .unwrap();
+    items.swap(pivot, j);
+    items[i..].reverse();
+    true
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every sequence of `length` choices from an alphabet of `0..base`, e.g. the operators between
+/// the numbers of an equation. Counts like an odometer, with the last position changing fastest.
+#[derive(Clone, Debug)]
+pub struct CartesianPower {
+    base: usize,
+    indices: Vec<usize>,
+    changed_from: usize,
+    started: bool,
+}
+
+impl CartesianPower {
+    pub fn new(base: usize, length: usize) -> Self {
+        CartesianPower {
+            base,
+            indices: vec![0; length],
+            changed_from: 0,
+            started: false,
+        }
+    }
+
+    /// The next sequence, `None` after the last.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if !self.started {
+            self.started = true;
+            return (self.base > 0 || self.indices.is_empty()).then_some(&self.indices[..]);
+        }
+        let i = self
+            .indices
+            .iter()
+            .rposition(|&index| index + 1 < self.base)?;
+        self.indices[i] += 1;
+        self.indices[i + 1..].fill(0);
+        self.changed_from = i;
+        Some(&self.indices)
+    }
+
+    /// The current sequence, the one last returned by `next_indices`.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The first position of the current sequence that differs from the one before, `0` for the first.
+    /// Everything computed from the positions before it can be kept.
+    pub fn changed_from(&self) -> usize {
+        self.changed_from
+    }
+
+    /// Skip the remaining sequences that start like the current one up to and including `position`,
+    /// e.g. when that prefix already exceeds a target.
+    pub fn skip_prefix(&mut self, position: usize) {
+        self.indices[position + 1..].fill(self.base.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_permutation, pairs, CartesianPower, Combinations, Permutations};
+
+    fn collect(next: impl FnMut() -> Option<Vec<usize>>) -> Vec<Vec<usize>> {
+        std::iter::from_fn(next).collect()
+    }
+
+    #[test]
+    fn enumerates_pairs() {
+        let items = ['a', 'b', 'c', 'd'];
+        let mut iter = pairs(&items);
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some((&'a', &'b')));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(
+            iter.map(|(a, b)| format!("{a}{b}")).collect::<Vec<_>>(),
+            vec!["ac", "ad", "bc", "bd", "cd"]
+        );
+        assert_eq!(pairs(&[1]).count(), 0);
+        assert_eq!(pairs::<u8>(&[]).len(), 0);
+    }
+
+    #[test]
+    fn enumerates_combinations() {
+        let mut iter = Combinations::new(4, 2);
+        let all = collect(|| iter.next_indices().map(<[_]>::to_vec));
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+
+        let mut none = Combinations::new(2, 3);
+        assert_eq!(none.next_indices(), None);
+        let mut empty = Combinations::new(3, 0);
+        assert_eq!(empty.next_indices(), Some(&[][..]));
+        assert_eq!(empty.next_indices(), None);
+
+        let mut iter = Combinations::new(10, 4);
+        assert_eq!(
+            collect(|| iter.next_indices().map(<[_]>::to_vec)).len(),
+            210
+        );
+    }
+
+    #[test]
+    fn enumerates_permutations() {
+        let mut iter = Permutations::new(3);
+        let all = collect(|| iter.next_indices().map(<[_]>::to_vec));
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0]
+            ]
+        );
+        let mut iter = Permutations::new(0);
+        assert_eq!(iter.next_indices(), Some(&[][..]));
+        assert_eq!(iter.next_indices(), None);
+
+        // duplicates only come up once.
+        let mut letters = ['a', 'a', 'b'];
+        let mut seen = vec![letters.iter().collect::<String>()];
+        while next_permutation(&mut letters) {
+            seen.push(letters.iter().collect());
+        }
+        assert_eq!(seen, vec!["aab", "aba", "baa"]);
+        assert_eq!(letters, ['a', 'a', 'b']);
+    }
+
+    #[test]
+    fn enumerates_cartesian_powers() {
+        let mut iter = CartesianPower::new(3, 2);
+        let mut all = vec![];
+        let mut changes = vec![];
+        while let Some(indices) = iter.next_indices() {
+            all.push(indices.to_vec());
+            changes.push(iter.changed_from());
+        }
+        assert_eq!(all.len(), 9);
+        assert_eq!(all[..4], [vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 0]]);
+        assert_eq!(all[8], vec![2, 2]);
+        assert_eq!(changes, vec![0, 1, 1, 0, 1, 1, 0, 1, 1]);
+
+        let mut empty = CartesianPower::new(0, 0);
+        assert_eq!(empty.next_indices(), Some(&[][..]));
+        assert_eq!(empty.next_indices(), None);
+        assert_eq!(CartesianPower::new(0, 2).next_indices(), None);
+    }
+
+    #[test]
+    fn skips_prefixes() {
+        let mut iter = CartesianPower::new(2, 3);
+        let mut all = vec![];
+        while let Some(indices) = iter.next_indices() {
+            all.push(indices.to_vec());
+            if indices[..2] == [0, 1] {
+                iter.skip_prefix(1);
+            }
+        }
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 0, 0],
+                vec![0, 0, 1],
+                vec![0, 1, 0],
+                vec![1, 0, 0],
+                vec![1, 0, 1],
+                vec![1, 1, 0],
+                vec![1, 1, 1]
+            ]
+        );
+    }
+}
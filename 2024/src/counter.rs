@@ -0,0 +1,201 @@
+//! A multiset that counts how often each key occurs, e.g. the frequencies of numbers in a list.
+//!
+//! Counts are `u64` so they can track more items than fit into memory, like the stones of day 11:
+//! [`Counter::flat_map_counts`] evolves every distinct key once and carries its count to the keys it turns into.
+
+use std::{borrow::Borrow, collections::hash_map, hash::Hash};
+
+use crate::hash::FastMap;
+
+#[derive(Clone, Debug)]
+pub struct Counter<K> {
+    counts: FastMap<K, u64>,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter {
+            counts: FastMap::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one occurrence of `key`.
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    /// Count `n` occurrences of `key`.
+    pub fn add_n(&mut self, key: K, n: u64) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// How often `key` occurs, `0` if it was never added.
+    pub fn get<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Remove `key`, returning how often it occurred.
+    pub fn remove<Q>(&mut self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// The number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The sum of all counts.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// All keys with their counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.counts.iter().map(|(key, count)| (key, *count))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    /// All keys with their counts, the most common first. Keys with equal counts are ordered by key.
+    pub fn most_common(&self) -> Vec<(&K, u64)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        entries
+    }
+
+    /// Replace every key by the keys `f` returns for it, each inheriting the key's count.
+    /// `f` is called once per distinct key, regardless of its count.
+    pub fn flat_map_counts<T, I>(&self, mut f: impl FnMut(&K) -> I) -> Counter<T>
+    where
+        T: Eq + Hash,
+        I: IntoIterator<Item = T>,
+    {
+        let mut counter = Counter::new();
+        for (key, count) in self.iter() {
+            for mapped in f(key) {
+                counter.add_n(mapped, count);
+            }
+        }
+        counter
+    }
+
+    /// Replace every key by `f(key)`, merging the counts of keys that map to the same key.
+    pub fn map_keys<T: Eq + Hash>(&self, mut f: impl FnMut(&K) -> T) -> Counter<T> {
+        self.flat_map_counts(|key| [f(key)])
+    }
+}
+
+impl<K: Eq + Hash> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Eq + Hash> Eq for Counter<K> {}
+
+impl<K: Eq + Hash> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Eq + Hash> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type Item = (K, u64);
+    type IntoIter = hash_map::IntoIter<K, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn counts_keys() {
+        let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+
+        counter.add('z');
+        counter.add_n('b', 3);
+        counter.add_n('y', 0);
+        assert_eq!(counter.get(&'b'), 5);
+        assert_eq!(counter.get(&'y'), 0);
+        assert_eq!(counter.remove(&'z'), 1);
+        assert_eq!(counter.remove(&'z'), 0);
+
+        assert_eq!(
+            counter.most_common(),
+            vec![(&'a', 5), (&'b', 5), (&'r', 2), (&'c', 1), (&'d', 1)]
+        );
+    }
+
+    #[test]
+    fn looks_up_borrowed_keys() {
+        let counter = ["ab", "cd", "ab"]
+            .map(String::from)
+            .into_iter()
+            .collect::<Counter<_>>();
+        assert_eq!(counter.get("ab"), 2);
+        assert_eq!(counter.into_iter().map(|(_, n)| n).sum::<u64>(), 3);
+    }
+
+    #[test]
+    fn carries_counts_through_maps() {
+        let counter = [1, 2, 2, 3, 3, 3].into_iter().collect::<Counter<u32>>();
+
+        let parity = counter.map_keys(|n| n % 2);
+        assert_eq!((parity.get(&0), parity.get(&1)), (2, 4));
+
+        let mut calls = 0;
+        let split = counter.flat_map_counts(|&n| {
+            calls += 1;
+            vec![n; n as usize]
+        });
+        assert_eq!(calls, 3);
+        assert_eq!(split.get(&3), 9);
+        assert_eq!(split.total(), 1 + 4 + 9);
+
+        let dropped = counter.flat_map_counts(|&n| (n != 2).then_some(n));
+        assert_eq!(dropped.total(), 4);
+    }
+}
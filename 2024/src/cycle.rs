@@ -0,0 +1,258 @@
+//! Cycle detection for simulations that eventually repeat a state, e.g. a guard walking in a loop.
+//!
+//! Like [`std::iter::successors`], the step functions return `None` when the simulation ends,
+//! in which case there is no cycle. [`brent`] and [`floyd`] only keep a few states in memory and compare
+//! them for equality; [`History`] hashes every state it sees, which also allows looking them up again
+//! to fast-forward with [`nth_state`].
+
+use std::hash::Hash;
+
+use crate::hash::FastMap;
+
+/// The states from step `start` on repeat every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Find the cycle of the states starting at `initial` with Brent's algorithm.
+/// Needs fewer steps than [`floyd`], about `start + 2 * length` in the worst case.
+pub fn brent<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> Option<T>) -> Option<Cycle> {
+    // find the length: the hare runs ahead, the tortoise teleports to it at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // find the start: with the hare `length` steps ahead, both meet at the first repeated state.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Find the cycle of the states starting at `initial` with Floyd's tortoise and hare.
+pub fn floyd<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> Option<T>) -> Option<Cycle> {
+    // the hare moves twice as fast, so both meet within the cycle.
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        hare = step(&hare)?;
+    }
+
+    // the distance to the meeting point is a multiple of the length, so walking on from
+    // there and from the beginning at the same speed meets at the start.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Every state of a simulation, in order, until one repeats.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    steps: FastMap<T, usize>,
+    states: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History {
+            steps: FastMap::default(),
+            states: vec![],
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> History<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the state of the next step. If the state was seen before, it is not recorded and the cycle is returned.
+    pub fn push(&mut self, state: T) -> Option<Cycle> {
+        let step = self.states.len();
+        match self.steps.get(&state) {
+            Some(&start) => Some(Cycle {
+                start,
+                length: step - start,
+            }),
+            None => {
+                self.steps.insert(state.clone(), step);
+                self.states.push(state);
+                None
+            }
+        }
+    }
+
+    /// The number of recorded steps.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// The state at `step`, if it was recorded.
+    pub fn get(&self, step: usize) -> Option<&T> {
+        self.states.get(step)
+    }
+
+    /// The step at which `state` was recorded.
+    pub fn step_of(&self, state: &T) -> Option<usize> {
+        self.steps.get(state).copied()
+    }
+
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+}
+
+/// Record the states starting at `initial` until one repeats.
+/// Returns `None` if the simulation ends first.
+pub fn find_cycle<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<(Cycle, History<T>)> {
+    let mut history = History::new();
+    let mut state = initial;
+
+    loop {
+        let next = step(&state);
+        if let Some(cycle) = history.push(state) {
+            return Some((cycle, history));
+        }
+        state = next?;
+    }
+}
+
+/// The state after `n` steps, skipping ahead once the states start to repeat.
+/// Returns `None` if the simulation ends before step `n`.
+pub fn nth_state<T: Clone + Eq + Hash>(
+    initial: T,
+    n: usize,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<T> {
+    let mut history = History::new();
+    let mut state = initial;
+
+    for _ in 0..n {
+        let next = step(&state)?;
+        if let Some(cycle) = history.push(state) {
+            return history.get(cycle.reduce(n)).cloned();
+        }
+        state = next;
+    }
+
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, find_cycle, floyd, nth_state, Cycle};
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn rho(n: &u32) -> Option<u32> {
+        Some(if *n == 6 { 3 } else { n + 1 })
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = Some(Cycle {
+            start: 3,
+            length: 4,
+        });
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(find_cycle(0, rho).map(|(cycle, _)| cycle), expected);
+
+        // a fixed point is a cycle of length 1.
+        let fixed = Some(Cycle {
+            start: 0,
+            length: 1,
+        });
+        assert_eq!(brent(7, |n| Some(*n)), fixed);
+        assert_eq!(floyd(7, |n| Some(*n)), fixed);
+
+        // the sequence ends before a state repeats.
+        let ends = |n: &u32| (*n < 10).then_some(n + 1);
+        assert_eq!(brent(0, ends), None);
+        assert_eq!(floyd(0, ends), None);
+        assert!(find_cycle(0, ends).is_none());
+    }
+
+    #[test]
+    fn agrees_on_random_maps() {
+        // x -> x² + 1 mod m gives cycles of all kinds of starts and lengths.
+        for m in 2..200_u64 {
+            let step = |x: &u64| Some((x * x + 1) % m);
+            let (cycle, history) = find_cycle(0, step).unwrap();
+            assert_eq!(brent(0, step), Some(cycle));
+            assert_eq!(floyd(0, step), Some(cycle));
+            assert_eq!(history.len(), cycle.start + cycle.length);
+        }
+    }
+
+    #[test]
+    fn fast_forwards() {
+        let (cycle, history) = find_cycle(0, rho).unwrap();
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(7), 3);
+        assert_eq!(cycle.reduce(1_000_000_001), 5);
+        assert_eq!(history.get(cycle.reduce(1_000_000_001)), Some(&5));
+        assert_eq!(history.step_of(&6), Some(6));
+
+        assert_eq!(nth_state(0, 0, rho), Some(0));
+        assert_eq!(nth_state(0, 5, rho), Some(5));
+        assert_eq!(nth_state(0, 1_000_000_001, rho), Some(5));
+        assert_eq!(nth_state(0, 5, |n| (*n < 3).then_some(n + 1)), None);
+        assert_eq!(nth_state(0, 3, |n| (*n < 3).then_some(n + 1)), Some(3));
+    }
+}
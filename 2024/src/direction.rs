@@ -0,0 +1,370 @@
+//! Directions on a grid, in the screen coordinates of [`crate::grid::Grid`] and [`crate::point::Point`]:
+//! up is `y - 1`, turning right is turning clockwise.
+//!
+//! Every direction maps to an index in `0..COUNT`, so directions can key arrays, e.g. `[bool; Dir4::COUNT]`
+//! to track which directions a search has visited a position from.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+use crate::point::Point;
+
+/// The four orthogonal directions, in clockwise order starting with up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// The eight orthogonal and diagonal directions, in clockwise order starting with up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// A change of direction, relative to the current direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Straight,
+    Right,
+    Around,
+    Left,
+}
+
+impl Dir4 {
+    pub const COUNT: usize = 4;
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Dir4::ALL.into_iter()
+    }
+
+    /// The position of this direction in [`Dir4::ALL`].
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Dir4::ALL.get(index).copied()
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    #[must_use]
+    pub const fn turn(self, turn: Turn) -> Self {
+        Dir4::ALL[(self as usize + turn as usize) % 4]
+    }
+
+    /// The turn that changes direction from `self` to `other`.
+    pub const fn turn_to(self, other: Self) -> Turn {
+        Turn::ALL[(other as usize + 4 - self as usize) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    /// The step of one move in this direction.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Dir4::Up => (0, -1),
+            Dir4::Right => (1, 0),
+            Dir4::Down => (0, 1),
+            Dir4::Left => (-1, 0),
+        };
+        Point::new(T::from(x), T::from(y))
+    }
+
+    /// Parse an arrow (`^>v<`), compass (`NESW`) or instruction (`UDLR`) character.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Dir4::Up),
+            '>' | 'E' | 'R' => Some(Dir4::Right),
+            'v' | 'S' | 'D' => Some(Dir4::Down),
+            '<' | 'W' | 'L' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow character of this direction.
+    pub const fn to_char(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+impl Dir8 {
+    pub const COUNT: usize = 8;
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Dir8::ALL.into_iter()
+    }
+
+    /// The position of this direction in [`Dir8::ALL`].
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Dir8::ALL.get(index).copied()
+    }
+
+    /// Turn clockwise by 45°.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turn counter-clockwise by 45°.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The step of one move in this direction.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        };
+        Point::new(T::from(x), T::from(y))
+    }
+}
+
+impl Turn {
+    pub const ALL: [Turn; 4] = [Turn::Straight, Turn::Right, Turn::Around, Turn::Left];
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<char> for Dir4 {
+    type Error = ParseDirError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Dir4::from_char(c).ok_or(ParseDirError)
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir4::try_from(c),
+            _ => Err(ParseDirError),
+        }
+    }
+}
+
+/// Parses compass directions, e.g. `N` or `SW`.
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "N" => Ok(Dir8::Up),
+            "NE" => Ok(Dir8::UpRight),
+            "E" => Ok(Dir8::Right),
+            "SE" => Ok(Dir8::DownRight),
+            "S" => Ok(Dir8::Down),
+            "SW" => Ok(Dir8::DownLeft),
+            "W" => Ok(Dir8::Left),
+            "NW" => Ok(Dir8::UpLeft),
+            _ => Err(ParseDirError),
+        }
+    }
+}
+
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// An error which can be returned when parsing a direction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirError;
+
+impl Error for ParseDirError {}
+
+impl Display for ParseDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `^>v<`, `NESW` or `UDLR`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: From<i8> + Add<Output = T>> Add<Dir4> for Point<T> {
+    type Output = Self;
+
+    fn add(self, dir: Dir4) -> Self::Output {
+        self + dir.delta()
+    }
+}
+
+impl<T: From<i8> + Add<Output = T>> Add<Dir8> for Point<T> {
+    type Output = Self;
+
+    fn add(self, dir: Dir8) -> Self::Output {
+        self + dir.delta()
+    }
+}
+
+impl<T: From<i8> + AddAssign> AddAssign<Dir4> for Point<T> {
+    fn add_assign(&mut self, dir: Dir4) {
+        *self += dir.delta();
+    }
+}
+
+impl<T: From<i8> + AddAssign> AddAssign<Dir8> for Point<T> {
+    fn add_assign(&mut self, dir: Dir8) {
+        *self += dir.delta();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir4, Dir8, ParseDirError, Turn};
+    use crate::point::Point;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Right.opposite(), Dir4::Left);
+        assert_eq!(Dir4::Down.turn(Turn::Around), Dir4::Up);
+        assert_eq!(Dir4::Down.turn(Turn::Straight), Dir4::Down);
+        for dir in Dir4::iter() {
+            assert_eq!(dir.turn(Turn::Left), dir.turn_left());
+            assert_eq!(dir.turn(Turn::Right), dir.turn_right());
+            for other in Dir4::iter() {
+                assert_eq!(dir.turn(dir.turn_to(other)), other);
+            }
+        }
+
+        assert_eq!(Dir8::Up.turn_right(), Dir8::UpRight);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+    }
+
+    #[test]
+    fn maps_indices() {
+        for (i, dir) in Dir4::iter().enumerate() {
+            assert_eq!(dir.index(), i);
+            assert_eq!(Dir4::from_index(i), Some(dir));
+        }
+        assert_eq!(Dir4::from_index(Dir4::COUNT), None);
+        assert_eq!(Dir8::iter().count(), Dir8::COUNT);
+        assert_eq!(Dir8::from_index(3), Some(Dir8::DownRight));
+    }
+
+    #[test]
+    fn computes_deltas() {
+        assert_eq!(Dir4::Up.delta::<i32>(), Point::new(0, -1));
+        assert_eq!(Dir4::Right.delta::<i64>(), Point::new(1, 0));
+        assert_eq!(Dir8::DownLeft.delta::<isize>(), Point::new(-1, 1));
+        for dir in Dir8::iter() {
+            assert_eq!(dir.delta::<i32>(), -dir.opposite().delta::<i32>());
+            assert_eq!(
+                dir.is_diagonal(),
+                dir.delta::<i32>().x != 0 && dir.delta::<i32>().y != 0
+            );
+        }
+        for dir in Dir4::iter() {
+            assert_eq!(Dir8::from(dir).delta::<i32>(), dir.delta::<i32>());
+        }
+
+        let mut point = Point::new(2_i32, 2) + Dir4::Up;
+        point += Dir8::DownRight;
+        assert_eq!(point, Point::new(3, 2));
+    }
+
+    #[test]
+    fn parses_chars() {
+        assert_eq!(
+            "^>v<".chars().map(Dir4::from_char).collect::<Vec<_>>(),
+            Dir4::ALL.map(Some)
+        );
+        assert_eq!(
+            "NESW".chars().map(Dir4::from_char).collect::<Vec<_>>(),
+            Dir4::ALL.map(Some)
+        );
+        assert_eq!(
+            "URDL".chars().map(Dir4::from_char).collect::<Vec<_>>(),
+            Dir4::ALL.map(Some)
+        );
+        assert_eq!(Dir4::try_from('x'), Err(ParseDirError));
+        assert_eq!("v".parse::<Dir4>(), Ok(Dir4::Down));
+        assert!("vv".parse::<Dir4>().is_err());
+        assert_eq!("SW".parse::<Dir8>(), Ok(Dir8::DownLeft));
+        assert_eq!(Dir4::Left.to_string(), "<");
+    }
+}
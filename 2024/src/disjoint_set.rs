@@ -0,0 +1,290 @@
+//! Disjoint sets (union-find) for tracking connected components, e.g. regions of a grid.
+//!
+//! [`DisjointSet`] works on dense indices `0..len`, such as the flat cell indices of a [`crate::grid::Grid`].
+//! [`KeyedDisjointSet`] wraps it for arbitrary hashable nodes.
+
+use std::hash::Hash;
+
+use crate::hash::FastMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Create `len` singleton sets.
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Add a new singleton set, returning its index.
+    pub fn push(&mut self) -> usize {
+        let index = self.parent.len();
+        self.parent.push(index);
+        self.size.push(1);
+        self.components += 1;
+        index
+    }
+
+    /// The representative of the set containing `x`. Compresses the path to it along the way.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+
+        root
+    }
+
+    /// Like [`DisjointSet::find`], but without path compression, for when only a shared reference is available.
+    pub fn root(&self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // attach the smaller tree below the larger one, which keeps trees shallow.
+        let (large, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set containing `x`.
+    pub fn size_of(&self, x: usize) -> usize {
+        self.size[self.root(x)]
+    }
+
+    /// The number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// All sets, each with its elements in ascending order. Sets are ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root =
+            FastMap::with_capacity_and_hasher(self.components, Default::default());
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let i = *index_of_root.entry(root).or_insert_with(|| {
+                components.push(Vec::with_capacity(self.size[root]));
+                components.len() - 1
+            });
+            components[i].push(x);
+        }
+
+        components
+    }
+}
+
+/// A [`DisjointSet`] over arbitrary hashable nodes. Nodes are added on first use.
+#[derive(Clone, Debug)]
+pub struct KeyedDisjointSet<K> {
+    set: DisjointSet,
+    indices: FastMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        KeyedDisjointSet {
+            set: DisjointSet::default(),
+            indices: FastMap::default(),
+            keys: vec![],
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Add `key` as a singleton set if it is not present yet, returning its index.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+        let index = self.set.push();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        index
+    }
+
+    /// The representative of the set containing `key`.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        let root = self.set.find(index);
+        Some(&self.keys[root])
+    }
+
+    /// Merge the sets containing `a` and `b`, adding them first if needed.
+    /// Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.set.union(a, b)
+    }
+
+    /// Whether `a` and `b` are in the same set. Nodes that were never added are only in the same set as themselves.
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (*a, *b);
+                self.set.same(a, b)
+            }
+            _ => a == b,
+        }
+    }
+
+    /// The number of nodes in the set containing `key`, or `0` if it was never added.
+    pub fn size_of(&self, key: &K) -> usize {
+        self.indices.get(key).map_or(0, |i| self.set.size_of(*i))
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.set.component_count()
+    }
+
+    /// All sets, with nodes in insertion order. Sets are ordered by their first inserted node.
+    pub fn components(&mut self) -> Vec<Vec<&K>> {
+        self.set
+            .components()
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| &self.keys[i]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjointSet, KeyedDisjointSet};
+    use crate::grid::Grid;
+
+    #[test]
+    fn unions_sets() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.component_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(!set.union(0, 2));
+        assert!(set.union(4, 5));
+
+        assert!(set.same(0, 2));
+        assert!(!set.same(0, 3));
+        assert_eq!(set.size_of(1), 3);
+        assert_eq!(set.size_of(3), 1);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.components(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+
+        let index = set.push();
+        assert_eq!(index, 6);
+        assert_eq!(set.component_count(), 4);
+    }
+
+    #[test]
+    fn compresses_paths() {
+        let mut set = DisjointSet::new(1000);
+        for i in 1..1000 {
+            set.union(i - 1, i);
+        }
+        let root = set.find(999);
+        assert!((0..1000).all(|i| set.root(i) == root));
+        assert_eq!(set.size_of(0), 1000);
+    }
+
+    #[test]
+    fn groups_grid_regions() {
+        let grid = Grid::parse("AAB\nACB\nCCB", |c| c).unwrap();
+        let mut set = DisjointSet::new(grid.len());
+        for ((x, y), plant) in grid.iter() {
+            for (nx, ny) in grid.neighbors4(x, y) {
+                if grid[(nx, ny)] == *plant {
+                    set.union(y * grid.width() + x, ny * grid.width() + nx);
+                }
+            }
+        }
+
+        let sizes = set.components().iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn unions_keyed_sets() {
+        let mut set = KeyedDisjointSet::new();
+        set.union("a", "b");
+        set.union("c", "d");
+        set.insert("e");
+        assert!(set.union("b", "d"));
+
+        assert_eq!(set.len(), 5);
+        assert!(set.same(&"a", &"c"));
+        assert!(!set.same(&"a", &"e"));
+        assert!(!set.same(&"a", &"z"));
+        assert!(set.same(&"z", &"z"));
+        assert_eq!(set.size_of(&"d"), 4);
+        assert_eq!(set.size_of(&"z"), 0);
+        assert_eq!(set.find(&"z"), None);
+        let root = set.find(&"a").copied();
+        assert_eq!(set.find(&"d").copied(), root);
+        assert_eq!(set.component_count(), 2);
+        assert_eq!(
+            set.components(),
+            vec![vec![&"a", &"b", &"c", &"d"], vec![&"e"]]
+        );
+    }
+}
@@ -0,0 +1,514 @@
+//! A dense, two-dimensional grid of cells, as found in most puzzle inputs.
+//!
+//! Cells are stored row-major in a flat `Vec`. Positions are `(x, y)` pairs, where `x` is the column
+//! and `y` is the row, with `(0, 0)` in the top left corner. Lookups take signed coordinates, so
+//! callers can step off the grid and get `None` back instead of checking bounds themselves.
+
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    point::Point,
+    tile::{Tile, UnknownTileError},
+};
+
+/// Offsets of the four orthogonal neighbors: up, right, down, left.
+pub static NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbors, clockwise starting with up.
+pub static NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from row-major `cells`. Returns `None` if `cells` does not fill `width` columns evenly.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Grid {
+                width,
+                height: 0,
+                cells,
+            });
+        }
+
+        cells.len().is_multiple_of(width).then(|| Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parse a grid from lines of text, mapping every character to a cell with `f`.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        Self::try_parse(input, |c| Ok::<_, Infallible>(f(c))).map_err(|e| match e {
+            TryParseGridError::Shape(e) => e,
+            TryParseGridError::Cell { error, .. } => match error {},
+        })
+    }
+
+    /// Parse a grid from lines of text like [`Grid::parse`], but `f` may reject characters.
+    pub fn try_parse<E>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, TryParseGridError<E>> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (row, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|error| TryParseGridError::Cell { x, y: row, error })?);
+            }
+            let found = cells.len() - len_before;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(TryParseGridError::Shape(ParseGridError {
+                        row,
+                        expected,
+                        found,
+                    }));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let width = width.unwrap_or(0);
+        Ok(Grid {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    /// Parse a grid of [`Tile`]s, e.g. an enum declared with [`crate::tiles!`].
+    pub fn parse_tiles(input: &str) -> Result<Self, TryParseGridError<UnknownTileError>>
+    where
+        T: Tile,
+    {
+        Self::try_parse(input, T::try_from)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `(x, y)` lies on the grid.
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        self.index_of(x, y).is_some()
+    }
+
+    /// The index of `(x, y)` in the flat cell storage, if it lies on the grid.
+    pub fn index_of(&self, x: isize, y: isize) -> Option<usize> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// The position of an index into the flat cell storage.
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// The cell at `point`, if it lies on the grid.
+    pub fn get_point<C: TryInto<usize>>(&self, point: Point<C>) -> Option<&T> {
+        let (x, y) = point.to_index()?;
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_point_mut<C: TryInto<usize>>(&mut self, point: Point<C>) -> Option<&mut T> {
+        let (x, y) = point.to_index()?;
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Iterate all cells in row-major order, together with their position.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// The position of the first cell, in row-major order, that matches `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.position_of(i))
+    }
+
+    /// The positions of all cells that match `predicate`, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(i, _)| self.position_of(i))
+    }
+
+    /// Positions next to `(x, y)` at the given offsets that lie on the grid.
+    pub fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(*dx)?;
+            let ny = y.checked_add_signed(*dy)?;
+            (nx < self.width && ny < self.height).then_some((nx, ny))
+        })
+    }
+
+    /// The orthogonal neighbors of `(x, y)` that lie on the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &NEIGHBORS_4)
+    }
+
+    /// The orthogonal and diagonal neighbors of `(x, y)` that lie on the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &NEIGHBORS_8)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` panics for a width of zero, which only an empty grid has.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells from `(x, y)` in steps of `(dx, dy)` until the edge of the grid, starting with `(x, y)` itself.
+    pub fn ray(&self, x: isize, y: isize, dx: isize, dy: isize) -> impl Iterator<Item = &T> {
+        (0..)
+            .map(move |i| self.get(x + dx * i, y + dy * i))
+            .take_while(Option::is_some)
+            .flatten()
+    }
+
+    /// Diagonals running from top left to bottom right, starting with the one through the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..width + height - 1).map(move |i| {
+            let (x, y) = if i < height {
+                (0, height - 1 - i)
+            } else {
+                (i - height + 1, 0)
+            };
+            self.ray(x, y, 1, 1)
+        })
+    }
+
+    /// Diagonals running from top right to bottom left, starting with the one through the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..width + height - 1).map(move |i| {
+            let (x, y) = if i < width {
+                (i, 0)
+            } else {
+                (width - 1, i - width + 1)
+            };
+            self.ray(x, y, -1, 1)
+        })
+    }
+
+    /// Create a grid of the same size with every cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid of the given size with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of bounds"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of bounds"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T, C: TryInto<usize> + Copy + Display> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<C>) -> &Self::Output {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{point} is out of bounds"))
+    }
+}
+
+impl<T, C: TryInto<usize> + Copy + Display> IndexMut<Point<C>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<C>) -> &mut Self::Output {
+        self.get_point_mut(point)
+            .unwrap_or_else(|| panic!("{point} is out of bounds"))
+    }
+}
+
+/// Renders every row on its own line, with cells written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`] from rows of different lengths.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseGridError {
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+/// An error which can be returned by [`Grid::try_parse`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryParseGridError<E> {
+    /// The rows have different lengths.
+    Shape(ParseGridError),
+    /// The character in column `x` of row `y` was rejected.
+    Cell { x: usize, y: usize, error: E },
+}
+
+impl<E: Error> Error for TryParseGridError<E> {}
+
+impl<E: Display> Display for TryParseGridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryParseGridError::Shape(e) => e.fmt(f),
+            TryParseGridError::Cell { x, y, error } => write!(f, "row {y}, column {x}: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, ParseGridError};
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 'f');
+
+        let digits = Grid::parse("12\r\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.cells(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn parses_empty_grids() {
+        let grid = Grid::parse("", |c| c).unwrap();
+        assert!(grid.is_empty());
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            Grid::parse("abc\nde", |c| c).unwrap_err(),
+            ParseGridError {
+                row: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn builds_from_vec() {
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3, 4]).unwrap().height(), 2);
+        assert!(Grid::from_vec(3, vec![1, 2, 3, 4]).is_none());
+        assert_eq!(Grid::new(2, 3, 0).len(), 6);
+    }
+
+    #[test]
+    fn gets_signed_coordinates() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert!(grid.in_bounds(2, 1));
+
+        *grid.get_mut(1, 1).unwrap() = 'x';
+        grid[(0, 0)] = 'y';
+        assert_eq!(grid.to_string(), "ybc\ndxf");
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = Grid::new(3, 3, '.');
+        assert_eq!(
+            grid.neighbors4(1, 1).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid = Grid::parse("#.#\n.#.", |c| c).unwrap();
+        assert_eq!(grid.find(|c| *c == '.'), Some((1, 0)));
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (1, 1)]
+        );
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'#')));
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn iterates_rays_and_diagonals() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.ray(0, 0, 1, 0).collect::<String>(), "abc");
+        assert_eq!(grid.ray(2, 1, -1, -1).collect::<String>(), "fb");
+        assert_eq!(grid.ray(5, 5, 1, 0).count(), 0);
+        assert_eq!(
+            grid.diagonals()
+                .map(|d| d.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|d| d.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = get_mock_grid().map(|c| c.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "ABC\nDEF");
+    }
+}
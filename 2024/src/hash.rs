@@ -0,0 +1,132 @@
+//! A fast, non-cryptographic hasher for the small keys of puzzle solutions, e.g. coordinates or ids.
+//!
+//! [`FxHasher`] follows the hash used by rustc ("FxHash"): every word is mixed in with a rotate, xor and multiply.
+//! It is much cheaper than the `SipHash` of the standard library, but gives no protection against
+//! collisions crafted on purpose, which is fine for puzzle inputs. Hashes are deterministic, and
+//! [`FxBuildHasher::with_seed`] gives a different but still deterministic hash per seed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hasher},
+};
+
+/// A `HashMap` hashed with [`FxHasher`]. Create with `FastMap::default()`.
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` hashed with [`FxHasher`]. Create with `FastSet::default()`.
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.add_to_hash(i as u64);
+        self.add_to_hash((i >> 64) as u64);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds [`FxHasher`]s starting from a seed, `0` by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FxBuildHasher {
+    seed: u64,
+}
+
+impl FxBuildHasher {
+    pub fn with_seed(seed: u64) -> Self {
+        FxBuildHasher { seed }
+    }
+}
+
+impl BuildHasher for FxBuildHasher {
+    type Hasher = FxHasher;
+
+    fn build_hasher(&self) -> FxHasher {
+        FxHasher { hash: self.seed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::BuildHasher;
+
+    use super::{FastMap, FastSet, FxBuildHasher};
+
+    #[test]
+    fn hashes_deterministically() {
+        let default = FxBuildHasher::default();
+        assert_eq!(default.hash_one((3, 4)), default.hash_one((3, 4)));
+        assert_ne!(default.hash_one((3, 4)), default.hash_one((4, 3)));
+        assert_ne!(default.hash_one("abc"), default.hash_one("abd"));
+        assert_ne!(
+            default.hash_one(1_u64),
+            FxBuildHasher::with_seed(1).hash_one(1_u64)
+        );
+        assert_eq!(
+            FxBuildHasher::with_seed(1).hash_one("seeded"),
+            FxBuildHasher::with_seed(1).hash_one("seeded")
+        );
+    }
+
+    #[test]
+    fn works_as_collections() {
+        let mut map = FastMap::default();
+        for i in 0..1000_i32 {
+            *map.entry((i % 10, i % 7)).or_insert(0) += 1;
+        }
+        assert_eq!(map.len(), 70);
+        assert_eq!(map[&(3, 3)], 15);
+
+        let mut set = FastSet::with_hasher(FxBuildHasher::with_seed(42));
+        set.extend("the quick brown fox jumps over the lazy dog".split(' '));
+        assert_eq!(set.len(), 8);
+        assert!(set.contains("fox"));
+    }
+}
@@ -0,0 +1,376 @@
+//! Images of grids for debugging, written without any dependencies.
+//!
+//! An [`Image`] is built from a [`Grid`] or [`SparseGrid`] by mapping every cell to a color, e.g. with a
+//! [`Palette`]. It can be written as PBM (black and white), PPM or PNG. The PNG encoder does not compress,
+//! it stores the pixels in uncompressed deflate blocks, which every viewer can read.
+
+use std::{fs, hash::Hash, io, path::Path};
+
+use crate::{grid::Grid, hash::FastMap, point::Point, sparse_grid::SparseGrid};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Distinct colors that [`Palette`] hands out to values without a color of their own.
+const DISTINCT: [Rgb; 12] = [
+    [31, 119, 180],
+    [255, 127, 14],
+    [44, 160, 44],
+    [214, 39, 40],
+    [148, 103, 189],
+    [140, 86, 75],
+    [227, 119, 194],
+    [127, 127, 127],
+    [188, 189, 34],
+    [23, 190, 207],
+    [255, 215, 0],
+    [0, 0, 128],
+];
+
+/// Maps values to colors. Values without a color get the next of a list of distinct colors,
+/// in the order they are first seen, so e.g. every region of a map is told apart.
+#[derive(Clone, Debug)]
+pub struct Palette<T> {
+    colors: FastMap<T, Rgb>,
+    assigned: usize,
+}
+
+impl<T> Default for Palette<T> {
+    fn default() -> Self {
+        Palette {
+            colors: FastMap::default(),
+            assigned: 0,
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Palette<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always draw `value` in `color`.
+    pub fn insert(&mut self, value: T, color: Rgb) {
+        self.colors.insert(value, color);
+    }
+
+    pub fn color(&mut self, value: &T) -> Rgb {
+        if let Some(color) = self.colors.get(value) {
+            return *color;
+        }
+        let color = DISTINCT[self.assigned % DISTINCT.len()];
+        self.assigned += 1;
+        self.colors.insert(value.clone(), color);
+        color
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<(T, Rgb)> for Palette<T> {
+    fn from_iter<I: IntoIterator<Item = (T, Rgb)>>(iter: I) -> Self {
+        let mut palette = Palette::new();
+        for (value, color) in iter {
+            palette.insert(value, color);
+        }
+        palette
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// A `width` × `height` image filled with `color`.
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// One pixel per cell of `grid`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> Rgb) -> Self {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.cells().iter().map(&mut f).collect(),
+        }
+    }
+
+    /// One pixel per cell of the bounding box of `grid`. `f` gets `None` for empty cells.
+    pub fn from_sparse_grid<T>(grid: &SparseGrid<T>, mut f: impl FnMut(Option<&T>) -> Rgb) -> Self {
+        let Some((min, max)) = grid.bounds() else {
+            return Image::new(0, 0, BLACK);
+        };
+        let pixels = (min.y..=max.y)
+            .flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+            .map(|point| f(grid.get(point)))
+            .collect();
+        Image {
+            width: (max.x - min.x + 1) as usize,
+            height: (max.y - min.y + 1) as usize,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Set the pixel at `(x, y)`. Panics if it is outside of the image.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the image"
+        );
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// The image with every pixel blown up to a `factor` × `factor` square, as single pixels are hard to see.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let pixels = (0..self.height * factor)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+        Image {
+            width,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// A binary PBM, where pixels darker than mid grey are black.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut out = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for byte in row.chunks(8) {
+                let bits = byte.iter().enumerate().fold(0, |bits, (i, pixel)| {
+                    let luma =
+                        (299 * pixel[0] as u32 + 587 * pixel[1] as u32 + 114 * pixel[2] as u32)
+                            / 1000;
+                    bits | (u8::from(luma < 128) << (7 - i))
+                });
+                out.push(bits);
+            }
+        }
+        out
+    }
+
+    /// A binary PPM.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flatten());
+        out
+    }
+
+    /// An 8-bit RGB PNG with uncompressed image data.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // bit depth 8, color type RGB, default compression, filter and interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut out, b"IHDR", &header);
+
+        // every scanline starts with its filter type, 0 for none.
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// Write the image in the format of the extension of `path`: `pbm`, `ppm` or `png`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("pbm") => self.to_pbm(),
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a .pbm, .ppm or .png file", path.display()),
+                ))
+            }
+        };
+        fs::write(path, bytes)
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// `data` as a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate without compression, 32K window.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1, 0), |(a, b), byte| {
+        let a = (a + *byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adler32, crc32, zlib_stored, Image, Palette, BLACK, DISTINCT, WHITE};
+    use crate::{grid::Grid, point::Point, sparse_grid::SparseGrid};
+
+    /// Unpacks the uncompressed deflate blocks of a zlib stream.
+    fn inflate_stored(stream: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        let mut i = 2;
+        loop {
+            let last = stream[i] == 1;
+            let len = u16::from_le_bytes([stream[i + 1], stream[i + 2]]) as usize;
+            let nlen = u16::from_le_bytes([stream[i + 3], stream[i + 4]]) as usize;
+            assert_eq!(len ^ nlen, 0xffff);
+            data.extend(&stream[i + 5..i + 5 + len]);
+            i += 5 + len;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(stream[i..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stores_blocks() {
+        let data = (0..150_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        assert_eq!(inflate_stored(&zlib_stored(&data)), data);
+        assert_eq!(inflate_stored(&zlib_stored(&[])), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn writes_png() {
+        let grid = Grid::parse("#.\n.#\n..", |c| c == '#').unwrap();
+        let image = Image::from_grid(&grid, |wall| if *wall { BLACK } else { WHITE });
+        let png = image.to_png();
+
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(png[16..29], [0, 0, 0, 2, 0, 0, 0, 3, 8, 2, 0, 0, 0]);
+        assert_eq!(png[29..33], crc32(&png[12..29]).to_be_bytes());
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(png[37..41], *b"IDAT");
+        let raw = inflate_stored(&png[41..41 + idat_len]);
+        assert_eq!(
+            raw,
+            [
+                [0, 0, 0, 0, 255, 255, 255].as_slice(),
+                &[0, 255, 255, 255, 0, 0, 0],
+                &[0, 255, 255, 255, 255, 255, 255],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn writes_netpbm() {
+        let mut image = Image::new(10, 2, WHITE);
+        image.set(0, 0, BLACK);
+        image.set(9, 1, [10, 20, 30]);
+        assert_eq!(image.get(9, 1), Some([10, 20, 30]));
+        assert_eq!(image.get(10, 1), None);
+
+        let pbm = image.to_pbm();
+        assert_eq!(
+            pbm,
+            [b"P4\n10 2\n".as_slice(), &[0b1000_0000, 0, 0, 0b0100_0000]].concat()
+        );
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n10 2\n255\n"));
+        assert_eq!(ppm.len(), 12 + 10 * 2 * 3);
+        assert_eq!(ppm[ppm.len() - 3..], [10, 20, 30]);
+    }
+
+    #[test]
+    fn maps_grids() {
+        let grid = [(Point::new(-1, 5), 'a'), (Point::new(1, 6), 'b')]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+        let mut palette = [('a', WHITE)].into_iter().collect::<Palette<_>>();
+        let image = Image::from_sparse_grid(&grid, |cell| match cell {
+            Some(value) => palette.color(value),
+            None => BLACK,
+        });
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get(0, 0), Some(WHITE));
+        assert_eq!(image.get(2, 1), Some(DISTINCT[0]));
+        assert_eq!(image.get(1, 0), Some(BLACK));
+        assert_eq!(palette.color(&'c'), DISTINCT[1]);
+        assert_eq!(palette.color(&'b'), DISTINCT[0]);
+
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (6, 4));
+        assert_eq!(scaled.get(5, 3), Some(DISTINCT[0]));
+        assert_eq!(scaled.get(4, 1), Some(BLACK));
+    }
+}
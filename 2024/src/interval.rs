@@ -0,0 +1,388 @@
+//! A set of values stored as disjoint half-open spans `start..end`, e.g. the free blocks of a disk
+//! or the ranges covered by sensors.
+//!
+//! Overlapping and touching spans are merged on insert, so every value is part of exactly one span and
+//! spans are kept in ascending order. Lookups are `O(log n)` in the number of spans. Spans are also
+//! indexed by their length, so [`IntervalSet::first_fit`] takes `O(log n)` for each distinct length
+//! that is long enough, instead of scanning every span. Lengths are few in puzzles like day 9.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter::Sum,
+    ops::{Range, Sub},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    /// `start -> end` of every span.
+    spans: BTreeMap<T, T>,
+    /// `length -> starts` of every span.
+    lengths: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            spans: BTreeMap::new(),
+            lengths: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of spans.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// All spans in ascending order.
+    pub fn spans(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.spans.iter().map(|(start, end)| *start..*end)
+    }
+
+    /// Add all values of `range`, merging it with the spans it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+
+        if let Some((&before, &before_end)) = self.spans.range(..=start).next_back() {
+            if before_end >= start {
+                start = before;
+                end = end.max(before_end);
+            }
+        }
+
+        while let Some((&merged_start, &merged_end)) = self.spans.range(start..=end).next() {
+            self.take_span(merged_start);
+            end = end.max(merged_end);
+        }
+
+        self.put_span(start, end);
+    }
+
+    /// Remove all values of `range`, splitting the spans it partially covers.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&before, &before_end)) = self.spans.range(..range.start).next_back() {
+            if before_end > range.start {
+                self.put_span(before, range.start);
+                if before_end > range.end {
+                    self.put_span(range.end, before_end);
+                }
+            }
+        }
+
+        // what is left of a span is put back at `range.end`, after the spans still to remove.
+        while let Some((&start, &end)) = self.spans.range(range.clone()).next() {
+            self.take_span(start);
+            if end > range.end {
+                self.put_span(range.end, end);
+            }
+        }
+    }
+
+    /// The span containing `value`.
+    pub fn span_containing(&self, value: T) -> Option<Range<T>> {
+        let (start, end) = self.spans.range(..=value).next_back()?;
+        (*end > value).then_some(*start..*end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.span_containing(value).is_some()
+    }
+
+    /// Whether every value of `range` is in the set. An empty range is always covered.
+    pub fn covers(&self, range: Range<T>) -> bool {
+        range.is_empty()
+            || self
+                .span_containing(range.start)
+                .is_some_and(|span| span.end >= range.end)
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn overlaps(&self, range: Range<T>) -> bool {
+        self.spans_in(range).next().is_some()
+    }
+
+    /// The parts of the spans that lie within `range`, in ascending order.
+    pub fn spans_in(&self, range: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let first = self
+            .spans
+            .range(..range.start)
+            .next_back()
+            .filter(|(_, end)| **end > range.start);
+        let rest = (!range.is_empty()).then(|| self.spans.range(range.clone()));
+
+        first
+            .into_iter()
+            .chain(rest.into_iter().flatten())
+            .map(move |(start, end)| *start.max(&range.start)..*end.min(&range.end))
+            .filter(|span| !span.is_empty())
+    }
+
+    /// The parts of `range` that are not in the set, in ascending order.
+    pub fn gaps(&self, range: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut position = range.start;
+        let mut covered = self.spans_in(range.clone());
+        let mut done = range.is_empty();
+
+        std::iter::from_fn(move || {
+            while !done {
+                let gap = match covered.next() {
+                    Some(span) => {
+                        let gap = position..span.start;
+                        position = span.end;
+                        gap
+                    }
+                    None => {
+                        done = true;
+                        position..range.end
+                    }
+                };
+                if !gap.is_empty() {
+                    return Some(gap);
+                }
+            }
+            None
+        })
+    }
+
+    /// Split the set at `at`: `self` keeps the values below `at`, the returned set all others.
+    pub fn split_off(&mut self, at: T) -> IntervalSet<T> {
+        let moved = self
+            .spans
+            .range(at..)
+            .map(|(start, end)| (*start, *end))
+            .collect::<Vec<_>>();
+        let mut above = IntervalSet::new();
+        for (start, end) in moved {
+            self.take_span(start);
+            above.put_span(start, end);
+        }
+        if let Some((&start, &end)) = self.spans.iter().next_back() {
+            if end > at {
+                self.put_span(start, at);
+                above.put_span(at, end);
+            }
+        }
+        above
+    }
+
+    /// The first span with at least `len` values, e.g. the leftmost free space a file fits into.
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        let start = self
+            .lengths
+            .range(len..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()?;
+        Some(*start..self.spans[start])
+    }
+
+    /// Like [`IntervalSet::first_fit`], but only considers the parts of the spans within `range`.
+    pub fn first_fit_in(&self, range: Range<T>, len: T) -> Option<Range<T>> {
+        if range.is_empty() {
+            return None;
+        }
+
+        // the span reaching into `range` from the left is clipped, so its length is not indexed.
+        if let Some((_, &end)) = self.spans.range(..range.start).next_back() {
+            let clipped = range.start..end.min(range.end);
+            if end > range.start && clipped.end - clipped.start >= len {
+                return Some(clipped);
+            }
+        }
+
+        // the first span usually fits already, e.g. when moving single blocks.
+        let (&first, &end) = self.spans.range(range.clone()).next()?;
+        if end.min(range.end) - first >= len {
+            return Some(first..end.min(range.end));
+        }
+
+        let start = self
+            .lengths
+            .range(len..)
+            .filter_map(|(_, starts)| starts.range(range.start..range.end).next())
+            .min()?;
+        // only the last span within `range` can be clipped, and no span after it would fit either.
+        let span = *start..self.spans[start].min(range.end);
+        (span.end - span.start >= len).then_some(span)
+    }
+
+    /// Add the span `start..end`, replacing a span with the same start.
+    fn put_span(&mut self, start: T, end: T) {
+        self.take_span(start);
+        self.spans.insert(start, end);
+        self.lengths.entry(end - start).or_default().insert(start);
+    }
+
+    /// Remove the span beginning at `start`, returning its end.
+    fn take_span(&mut self, start: T) -> Option<T> {
+        let end = self.spans.remove(&start)?;
+        if let Some(starts) = self.lengths.get_mut(&(end - start)) {
+            starts.remove(&start);
+            if starts.is_empty() {
+                self.lengths.remove(&(end - start));
+            }
+        }
+        Some(end)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn total(&self) -> T {
+        self.spans().map(|span| span.end - span.start).sum()
+    }
+
+    /// The number of values of `range` that are in the set.
+    pub fn covered(&self, range: Range<T>) -> T {
+        self.spans_in(range).map(|span| span.end - span.start).sum()
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    fn spans(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.spans().map(|span| (span.start, span.end)).collect()
+    }
+
+    #[test]
+    fn merges_spans() {
+        let mut set = [5..8, 1..3, 10..12, 3..4]
+            .into_iter()
+            .collect::<IntervalSet<_>>();
+        assert_eq!(spans(&set), vec![(1, 4), (5, 8), (10, 12)]);
+
+        set.insert(7..10);
+        assert_eq!(spans(&set), vec![(1, 4), (5, 12)]);
+        set.insert(0..20);
+        assert_eq!(spans(&set), vec![(0, 20)]);
+        set.insert(3..3);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn removes_spans() {
+        let mut set = [0..10, 20..30].into_iter().collect::<IntervalSet<_>>();
+        set.remove(3..5);
+        assert_eq!(spans(&set), vec![(0, 3), (5, 10), (20, 30)]);
+        set.remove(8..25);
+        assert_eq!(spans(&set), vec![(0, 3), (5, 8), (25, 30)]);
+        set.remove(0..3);
+        set.remove(26..27);
+        assert_eq!(spans(&set), vec![(5, 8), (25, 26), (27, 30)]);
+        set.remove(-10..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn queries_coverage() {
+        let set = [0..5, 10..15].into_iter().collect::<IntervalSet<_>>();
+        assert!(set.contains(0));
+        assert!(!set.contains(5));
+        assert_eq!(set.span_containing(12), Some(10..15));
+        assert!(set.covers(1..5));
+        assert!(!set.covers(4..11));
+        assert!(set.covers(7..7));
+        assert!(set.overlaps(4..11));
+        assert!(!set.overlaps(5..10));
+
+        assert_eq!(set.total(), 10);
+        assert_eq!(set.covered(3..12), 4);
+        assert_eq!(set.spans_in(3..12).collect::<Vec<_>>(), vec![3..5, 10..12]);
+        assert_eq!(
+            set.gaps(-2..20).collect::<Vec<_>>(),
+            vec![-2..0, 5..10, 15..20]
+        );
+        assert_eq!(set.gaps(0..15).collect::<Vec<_>>(), vec![5..10]);
+        assert_eq!(set.gaps(1..3).count(), 0);
+    }
+
+    #[test]
+    fn finds_fitting_spans() {
+        // the free space of day 9's example `2333133121414131402`.
+        let free = [2..5, 8..11, 12..15, 18..19, 21..22, 26..27, 31..32, 35..36]
+            .into_iter()
+            .collect::<IntervalSet<i32>>();
+        assert_eq!(free.first_fit(1), Some(2..5));
+        assert_eq!(free.first_fit(4), None);
+        assert_eq!(free.first_fit_in(4..40, 3), Some(8..11));
+        assert_eq!(free.first_fit_in(0..10, 3), Some(2..5));
+        assert_eq!(free.first_fit_in(4..10, 3), None);
+    }
+
+    #[test]
+    fn indexes_lengths_through_changes() {
+        // compare against scanning every span after a series of pseudo-random changes.
+        let mut set = IntervalSet::new();
+        let mut seed = 7_u32;
+        let mut next = |n: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            ((seed >> 16) % n) as i32
+        };
+        for round in 0..500 {
+            let (start, len) = (next(200), next(12));
+            match round % 5 {
+                0 => {
+                    let above = set.split_off(start);
+                    set.extend(above.spans());
+                }
+                1 | 2 => set.remove(start..start + len),
+                _ => set.insert(start..start + len),
+            }
+            let (len, window) = (next(8), next(200)..next(200) + 50);
+            assert_eq!(
+                set.first_fit(len),
+                set.spans().find(|span| span.end - span.start >= len)
+            );
+            assert_eq!(
+                set.first_fit_in(window.clone(), len),
+                set.spans_in(window)
+                    .find(|span| span.end - span.start >= len)
+            );
+        }
+    }
+
+    #[test]
+    fn splits_sets() {
+        let mut set = [0..5, 10..15].into_iter().collect::<IntervalSet<_>>();
+        let above = set.split_off(12);
+        assert_eq!(spans(&set), vec![(0, 5), (10, 12)]);
+        assert_eq!(spans(&above), vec![(12, 15)]);
+
+        let above = set.split_off(5);
+        assert_eq!(spans(&set), vec![(0, 5)]);
+        assert_eq!(spans(&above), vec![(10, 12)]);
+    }
+}
@@ -1,3 +1,23 @@
+pub mod bitset;
+pub mod combinatorics;
+pub mod counter;
+pub mod cycle;
+pub mod direction;
+pub mod disjoint_set;
+pub mod grid;
+pub mod hash;
+pub mod image;
+pub mod interval;
+pub mod line;
+pub mod math;
+pub mod order;
+pub mod parse;
+pub mod point;
+pub mod scanner;
+pub mod search;
+pub mod sparse_grid;
 pub mod template;
+pub mod tile;
+pub mod visualizer;
 
 // Use this file to add helper functions and additional modules.
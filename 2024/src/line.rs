@@ -0,0 +1,300 @@
+//! Lines through lattice points, computed exactly with integers only.
+//!
+//! The points on a line through two lattice points that are lattice points themselves are spaced by the
+//! [`primitive_step`], the difference divided by the gcd of its coordinates. Lines and rays are clipped
+//! to bounds given as the top left and bottom right corner, both inclusive, like
+//! [`crate::sparse_grid::SparseGrid::bounds`]. Products are computed in `i128`, so any `i64` coordinates work.
+
+use crate::{math::gcd, point::Point};
+
+/// The shortest vector from `from` towards `to` between lattice points, `(0, 0)` if they are equal.
+pub fn primitive_step(from: Point<i64>, to: Point<i64>) -> Point<i64> {
+    let delta = to - from;
+    match gcd(delta.x, delta.y) {
+        0 => delta,
+        divisor => Point::new(delta.x / divisor, delta.y / divisor),
+    }
+}
+
+/// The z component of the cross product of two vectors: positive if `b` turns clockwise from `a`
+/// as the grid is printed, negative if counterclockwise and `0` if they are parallel.
+pub fn cross(a: Point<i64>, b: Point<i64>) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+fn dot(a: Point<i64>, b: Point<i64>) -> i128 {
+    a.x as i128 * b.x as i128 + a.y as i128 * b.y as i128
+}
+
+/// Whether the three points lie on one line.
+pub fn are_collinear(a: Point<i64>, b: Point<i64>, c: Point<i64>) -> bool {
+    cross(b - a, c - a) == 0
+}
+
+/// Whether `point` lies on the segment from `a` to `b`, including both ends.
+pub fn is_between(point: Point<i64>, a: Point<i64>, b: Point<i64>) -> bool {
+    are_collinear(a, b, point)
+        && (a.x.min(b.x)..=a.x.max(b.x)).contains(&point.x)
+        && (a.y.min(b.y)..=a.y.max(b.y)).contains(&point.y)
+}
+
+/// Every lattice point from `a` to `b`, including both ends.
+pub fn segment(a: Point<i64>, b: Point<i64>) -> impl Iterator<Item = Point<i64>> {
+    let step = primitive_step(a, b);
+    let steps = gcd((b - a).x, (b - a).y);
+    (0..=steps).map(move |t| a + step * t)
+}
+
+/// Every lattice point within `bounds` on the ray from `from` through `through`, ordered away from `from`.
+/// Just `from` if both are equal, or nothing if it is out of bounds.
+pub fn ray(
+    from: Point<i64>,
+    through: Point<i64>,
+    bounds: (Point<i64>, Point<i64>),
+) -> impl Iterator<Item = Point<i64>> {
+    let step = primitive_step(from, through);
+    let (first, last) = steps_within(from, step, bounds);
+    (first.max(0)..=last).map(move |t| from + step * t)
+}
+
+/// Every lattice point within `bounds` on the line through `a` and `b`, ordered from `a` towards `b`.
+/// Just `a` if both are equal, or nothing if it is out of bounds.
+pub fn line(
+    a: Point<i64>,
+    b: Point<i64>,
+    bounds: (Point<i64>, Point<i64>),
+) -> impl Iterator<Item = Point<i64>> {
+    let step = primitive_step(a, b);
+    let (first, last) = steps_within(a, step, bounds);
+    (first..=last).map(move |t| a + step * t)
+}
+
+/// The range of `t` with `start + step * t` within `bounds`, empty if there is none.
+fn steps_within(
+    start: Point<i64>,
+    step: Point<i64>,
+    (min, max): (Point<i64>, Point<i64>),
+) -> (i64, i64) {
+    let axis = |start: i64, step: i64, min: i64, max: i64| {
+        // mirror the axis so the step is not negative.
+        let (start, step, min, max) = if step < 0 {
+            (-start, -step, -max, -min)
+        } else {
+            (start, step, min, max)
+        };
+        match step {
+            0 if (min..=max).contains(&start) => (i64::MIN, i64::MAX),
+            0 => (1, 0),
+            step => (
+                -(start - min).div_euclid(step),
+                (max - start).div_euclid(step),
+            ),
+        }
+    };
+    let (x_first, x_last) = axis(start.x, step.x, min.x, max.x);
+    let (y_first, y_last) = axis(start.y, step.y, min.y, max.y);
+    let range = (x_first.max(y_first), x_last.min(y_last));
+    // a zero step stays in place, which is one point and not infinitely many.
+    if step == Point::new(0, 0) && range.0 <= range.1 {
+        (0, 0)
+    } else {
+        range
+    }
+}
+
+/// Where two segments meet, see [`segment_intersection`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intersection {
+    /// A single point, `(x / denominator, y / denominator)` in lowest terms with a positive denominator.
+    Point { x: i128, y: i128, denominator: i128 },
+    /// The segments are collinear and share the part between these points, ordered along the first segment.
+    Overlap(Point<i64>, Point<i64>),
+}
+
+impl Intersection {
+    /// The point the segments meet in, if it is a single lattice point.
+    pub fn lattice_point(&self) -> Option<Point<i64>> {
+        match *self {
+            Intersection::Point {
+                x,
+                y,
+                denominator: 1,
+            } => Some(Point::new(x.try_into().ok()?, y.try_into().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+/// Where the segment from `a` to `b` meets the one from `c` to `d`, ends included, `None` if they do not.
+pub fn segment_intersection(
+    a: Point<i64>,
+    b: Point<i64>,
+    c: Point<i64>,
+    d: Point<i64>,
+) -> Option<Intersection> {
+    let (r, s, ac) = (b - a, d - c, c - a);
+    let mut denominator = cross(r, s);
+
+    if denominator == 0 {
+        // parallel or degenerate: the shared part, if any, starts and ends at endpoints on both segments.
+        let direction = if r == Point::new(0, 0) { s } else { r };
+        let mut shared = [a, b, c, d]
+            .into_iter()
+            .filter(|&p| is_between(p, a, b) && is_between(p, c, d))
+            .collect::<Vec<_>>();
+        shared.sort_by_key(|&p| dot(p - a, direction));
+        let (&first, &last) = (shared.first()?, shared.last()?);
+        return Some(if first == last {
+            Intersection::Point {
+                x: first.x as i128,
+                y: first.y as i128,
+                denominator: 1,
+            }
+        } else {
+            Intersection::Overlap(first, last)
+        });
+    }
+
+    // a + r * t = c + s * u with t = t_numerator / denominator and u likewise, both within 0..=1.
+    let (mut t_numerator, mut u_numerator) = (cross(ac, s), cross(ac, r));
+    if denominator < 0 {
+        (denominator, t_numerator, u_numerator) = (-denominator, -t_numerator, -u_numerator);
+    }
+    if !(0..=denominator).contains(&t_numerator) || !(0..=denominator).contains(&u_numerator) {
+        return None;
+    }
+
+    let x = a.x as i128 * denominator + r.x as i128 * t_numerator;
+    let y = a.y as i128 * denominator + r.y as i128 * t_numerator;
+    let divisor = gcd_i128(gcd_i128(x, y), denominator);
+    Some(Intersection::Point {
+        x: x / divisor,
+        y: y / divisor,
+        denominator: denominator / divisor,
+    })
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        are_collinear, cross, is_between, line, primitive_step, ray, segment, segment_intersection,
+        Intersection,
+    };
+    use crate::point::Point;
+
+    fn p(x: i64, y: i64) -> Point<i64> {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn steps_between_lattice_points() {
+        assert_eq!(primitive_step(p(1, 1), p(7, 10)), p(2, 3));
+        assert_eq!(primitive_step(p(0, 0), p(-4, 0)), p(-1, 0));
+        assert_eq!(primitive_step(p(3, 3), p(3, 3)), p(0, 0));
+        assert_eq!(
+            segment(p(1, 1), p(7, 10)).collect::<Vec<_>>(),
+            vec![p(1, 1), p(3, 4), p(5, 7), p(7, 10)]
+        );
+        assert_eq!(segment(p(2, 2), p(2, 2)).collect::<Vec<_>>(), vec![p(2, 2)]);
+    }
+
+    #[test]
+    fn tests_positions() {
+        assert_eq!(cross(p(1, 0), p(0, 1)), 1);
+        assert!(are_collinear(p(0, 0), p(2, 4), p(-1, -2)));
+        assert!(!are_collinear(p(0, 0), p(2, 4), p(1, 3)));
+        assert!(is_between(p(1, 2), p(0, 0), p(2, 4)));
+        assert!(is_between(p(2, 4), p(0, 0), p(2, 4)));
+        assert!(!is_between(p(-1, -2), p(0, 0), p(2, 4)));
+        // far apart points would overflow an i64 cross product.
+        let far = i64::MAX / 2;
+        assert!(are_collinear(p(-far, -far), p(0, 0), p(far, far)));
+    }
+
+    #[test]
+    fn clips_to_bounds() {
+        let bounds = (p(0, 0), p(9, 9));
+        // the antennas of day 8's example at (4, 3) and (5, 5).
+        assert_eq!(
+            line(p(4, 3), p(5, 5), bounds).collect::<Vec<_>>(),
+            vec![p(3, 1), p(4, 3), p(5, 5), p(6, 7), p(7, 9)]
+        );
+        assert_eq!(
+            ray(p(4, 3), p(5, 5), bounds).collect::<Vec<_>>(),
+            vec![p(4, 3), p(5, 5), p(6, 7), p(7, 9)]
+        );
+        assert_eq!(
+            ray(p(5, 5), p(4, 3), bounds).collect::<Vec<_>>(),
+            vec![p(5, 5), p(4, 3), p(3, 1)]
+        );
+        assert_eq!(line(p(3, 0), p(3, 9), bounds).count(), 10);
+        assert_eq!(line(p(20, 0), p(20, 1), bounds).count(), 0);
+        // a line through points outside of the bounds can still cross them.
+        assert_eq!(line(p(-2, 12), p(-1, 11), bounds).next(), Some(p(1, 9)));
+        assert_eq!(ray(p(-2, 12), p(-1, 11), bounds).count(), 9);
+        assert_eq!(ray(p(-1, 11), p(-2, 12), bounds).count(), 0);
+        assert_eq!(ray(p(11, 5), p(12, 5), bounds).count(), 0);
+        assert_eq!(
+            line(p(4, 4), p(4, 4), bounds).collect::<Vec<_>>(),
+            vec![p(4, 4)]
+        );
+        assert_eq!(line(p(-4, 4), p(-4, 4), bounds).count(), 0);
+    }
+
+    #[test]
+    fn intersects_segments() {
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0)),
+            Some(Intersection::Point {
+                x: 2,
+                y: 2,
+                denominator: 1
+            })
+        );
+        let crossing = segment_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 0)).unwrap();
+        assert_eq!(
+            crossing,
+            Intersection::Point {
+                x: 1,
+                y: 1,
+                denominator: 2
+            }
+        );
+        assert_eq!(crossing.lattice_point(), None);
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(4, 0), p(4, 5))
+                .and_then(|i| i.lattice_point()),
+            Some(p(4, 0))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(5, 1), p(5, 5)),
+            None
+        );
+        // parallel but apart, and collinear but apart.
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(0, 1), p(4, 1)),
+            None
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 0), p(3, 0), p(5, 0)),
+            None
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(6, 0), p(8, 0), p(2, 0)),
+            Some(Intersection::Overlap(p(2, 0), p(6, 0)))
+        );
+        assert_eq!(
+            segment_intersection(p(2, 2), p(2, 2), p(0, 0), p(4, 4))
+                .and_then(|i| i.lattice_point()),
+            Some(p(2, 2))
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, progress, read, readme, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,7 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{export::Format, Day};
     use std::process;
 
     pub enum AppArguments {
@@ -17,6 +19,10 @@ mod args {
         Read {
             day: Day,
         },
+        Progress,
+        Readme {
+            check: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -27,14 +33,23 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            debug_frames: bool,
+            viz: bool,
+            fps: Option<u32>,
         },
         All {
             release: bool,
+            format: Format,
+            redact: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            machines: bool,
+            matrix: bool,
+            format: Format,
+            redact: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,15 +61,25 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                redact: args.contains("--redact"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let machines = args.contains("--machines");
+                let matrix = args.contains("--matrix");
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let redact = args.contains("--redact");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    machines,
+                    matrix,
+                    format,
+                    redact,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,6 +88,10 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("progress") => AppArguments::Progress,
+            Some("readme") => AppArguments::Readme {
+                check: args.contains("--check"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
@@ -73,6 +102,9 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                debug_frames: args.contains("--debug-frames"),
+                viz: args.contains("--viz"),
+                fps: args.opt_value_from_str("--fps")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,10 +134,27 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                format,
+                redact,
+            } => all::handle(release, format, redact),
+            AppArguments::Time { machines: true, .. } => time::handle_machines(),
+            AppArguments::Time {
+                matrix: true, day, ..
+            } => time::handle_matrix(day),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                format,
+                redact,
+                ..
+            } => time::handle(day, all, store, format, redact),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Progress => progress::handle(),
+            AppArguments::Readme { check } => readme::handle(check),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -121,7 +170,10 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                debug_frames,
+                viz,
+                fps,
+            } => solve::handle(day, release, dhat, submit, debug_frames, viz, fps),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,309 @@
+//! Integer math that comes up again and again: number theory, decimal digits and small linear systems.
+//!
+//! Everything is exact: intermediate results are computed in `i128` where products could overflow,
+//! and digits are counted with integer logarithms instead of floating point. Results that do not fit
+//! are reported instead of wrapping.
+
+use std::{error::Error, fmt::Display};
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i64
+}
+
+/// The least common multiple, always non-negative. Is `0` if either argument is `0`,
+/// and `None` if it does not fit into an `i64`.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. Returns `None` if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solve the system `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs (Chinese remainder theorem).
+/// Moduli must be positive but do not need to be coprime. Returns the smallest non-negative solution and the
+/// modulus of all solutions, the lcm of the moduli.
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64), CrtError> {
+    // both stay below `i64::MAX`, so their products with an `i64` fit into an `i128`.
+    let mut x: i128 = 0;
+    let mut modulus: i64 = 1;
+
+    for &(residue, m) in congruences {
+        if m <= 0 {
+            return Err(CrtError::InvalidModulus(m));
+        }
+        let (g, p, _) = extended_gcd(modulus, m);
+        let (residue, g, m) = (
+            i128::from(residue.rem_euclid(m)),
+            i128::from(g),
+            i128::from(m),
+        );
+
+        if (residue - x) % g != 0 {
+            return Err(CrtError::Contradiction);
+        }
+
+        // step from `x` in multiples of `modulus` until the new congruence holds.
+        let step = m / g;
+        let k = ((residue - x) / g * i128::from(p)).rem_euclid(step);
+        let combined = i128::from(modulus) * step;
+        x = (x + i128::from(modulus) * k).rem_euclid(combined);
+        modulus = combined.try_into().map_err(|_| CrtError::Overflow)?;
+    }
+
+    Ok((x as i64, modulus))
+}
+
+/// An error which can be returned by [`crt`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    /// The congruences contradict each other, there is no solution.
+    Contradiction,
+    /// A modulus is zero or negative.
+    InvalidModulus(i64),
+    /// There are solutions, but the lcm of the moduli does not fit into an `i64`.
+    Overflow,
+}
+
+impl Error for CrtError {}
+
+impl Display for CrtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrtError::Contradiction => write!(f, "the congruences contradict each other"),
+            CrtError::InvalidModulus(m) => write!(f, "modulus {m} is not positive"),
+            CrtError::Overflow => write!(f, "the lcm of the moduli does not fit into an i64"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The number of decimal digits of `n`. `0` has one digit.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// `10^exponent`, or `None` if it does not fit into a `u64`.
+pub fn pow10(exponent: u32) -> Option<u64> {
+    10_u64.checked_pow(exponent)
+}
+
+/// The decimal concatenation of `a` and `b`, e.g. `12 || 345 = 12345`. Returns `None` on overflow.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(pow10(digit_count(b))?)?.checked_add(b)
+}
+
+/// Split `n` into its leading digits and its last `digits` digits, e.g. `split_digits(12345, 2) = (123, 45)`.
+pub fn split_digits(n: u64, digits: u32) -> (u64, u64) {
+    match pow10(digits) {
+        Some(divisor) => (n / divisor, n % divisor),
+        None => (0, n),
+    }
+}
+
+/// Split `n` into two halves with the same number of digits, e.g. `1000 -> (10, 0)`.
+/// Returns `None` if `n` has an odd number of digits.
+pub fn split_half(n: u64) -> Option<(u64, u64)> {
+    let digits = digit_count(n);
+    digits
+        .is_multiple_of(2)
+        .then(|| split_digits(n, digits / 2))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of [`solve_linear`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinearSolution<const N: usize> {
+    /// The only solution, which is integral.
+    Unique([i64; N]),
+    /// The only solution has non-integer values.
+    NoInteger,
+    /// The matrix is singular: there is no solution or there are infinitely many.
+    Degenerate,
+}
+
+/// Solve `a · x = b` exactly for a small square system, e.g. two buttons and a prize.
+///
+/// Uses Cramer's rule with fraction-free determinants, so there is no rounding.
+/// Panics if a value of the unique solution does not fit into an `i64`.
+pub fn solve_linear<const N: usize>(a: [[i64; N]; N], b: [i64; N]) -> LinearSolution<N> {
+    let matrix = a.map(|row| row.map(i128::from));
+    let det = determinant(matrix);
+    if det == 0 {
+        return LinearSolution::Degenerate;
+    }
+
+    let mut solution = [0; N];
+    for (i, value) in solution.iter_mut().enumerate() {
+        let mut replaced = matrix;
+        for (row, b) in replaced.iter_mut().zip(b) {
+            row[i] = i128::from(b);
+        }
+        let det_i = determinant(replaced);
+        if det_i % det != 0 {
+            return LinearSolution::NoInteger;
+        }
+        *value = i64::try_from(det_i / det).expect("solution does not fit into an i64");
+    }
+
+    LinearSolution::Unique(solution)
+}
+
+/// The determinant of a square matrix, using Bareiss' fraction-free elimination.
+pub fn determinant<const N: usize>(mut m: [[i128; N]; N]) -> i128 {
+    if N == 0 {
+        return 1;
+    }
+
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+
+    for k in 0..N {
+        if m[k][k] == 0 {
+            match (k + 1..N).find(|&r| m[r][k] != 0) {
+                Some(r) => {
+                    m.swap(k, r);
+                    sign = -sign;
+                }
+                None => return 0,
+            }
+        }
+
+        for i in k + 1..N {
+            for j in k + 1..N {
+                // exact division, guaranteed by Sylvester's identity.
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous_pivot;
+            }
+        }
+        previous_pivot = m[k][k];
+    }
+
+    sign * m[N - 1][N - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(i64::MAX, i64::MAX), Some(i64::MAX));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        assert_eq!(lcm(i64::MIN, 1), None);
+
+        for (a, b) in [(240, 46), (-7, 3), (3, -7), (0, 5), (17, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_inverses() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt(&[(-1, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(CrtError::Contradiction));
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(crt(&[(1, 4), (0, 0)]), Err(CrtError::InvalidModulus(0)));
+        assert_eq!(crt(&[(1, -4)]), Err(CrtError::InvalidModulus(-4)));
+        assert_eq!(crt(&[(1, i64::MAX), (0, 2)]), Err(CrtError::Overflow));
+
+        // intermediate products of these moduli overflow an i64.
+        let (x, m) = crt(&[(1, 1_000_000_007), (2, 998_244_353)]).unwrap();
+        assert_eq!(m, 1_000_000_007 * 998_244_353);
+        assert_eq!((x % 1_000_000_007, x % 998_244_353), (1, 2));
+    }
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(999_999_999_999_999_999), 18);
+        assert_eq!(digit_count(u64::MAX), 20);
+
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(15, 0), Some(150));
+        assert_eq!(concat(u64::MAX / 10, 5), Some(u64::MAX));
+        assert_eq!(concat(u64::MAX / 10, 6), None);
+
+        assert_eq!(split_digits(12345, 2), (123, 45));
+        assert_eq!(split_digits(12345, 0), (12345, 0));
+        assert_eq!(split_digits(12345, 25), (0, 12345));
+        assert_eq!(split_half(1000), Some((10, 0)));
+        assert_eq!(split_half(253000), Some((253, 0)));
+        assert_eq!(split_half(123), None);
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        // the claw machines of day 13: a * button_a + b * button_b = prize.
+        assert_eq!(
+            solve_linear([[94, 22], [34, 67]], [8400, 5400]),
+            LinearSolution::Unique([80, 40])
+        );
+        assert_eq!(
+            solve_linear([[26, 67], [66, 21]], [12748, 12176]),
+            LinearSolution::NoInteger
+        );
+        assert_eq!(
+            solve_linear([[1, 2], [2, 4]], [3, 6]),
+            LinearSolution::Degenerate
+        );
+
+        // a zero pivot needs a row swap.
+        assert_eq!(
+            solve_linear([[0, 1, 0], [1, 0, 0], [0, 0, 2]], [5, -3, 8]),
+            LinearSolution::Unique([-3, 5, 4])
+        );
+        assert_eq!(determinant([[2, 0, 1], [1, 3, 2], [1, 1, 1]]), 0);
+        assert_eq!(determinant([[6, 1, 1], [4, -2, 5], [2, 8, 7]]), -306);
+        assert_eq!(determinant::<0>([]), 1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Orderings from precedence rules `before|after`, e.g. which pages of a manual have to be printed first.
+//!
+//! [`Precedence`] only keeps the rules it was given, so it describes a partial order that may not even be
+//! consistent. [`Precedence::toposort`] orders a subset of the nodes with Kahn's algorithm, only considering
+//! the rules between nodes of that subset, and reports a [`CycleError`] if those rules contradict each other.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use crate::hash::{FastMap, FastSet};
+
+/// Precedence rules that require each node of `cycle` to come before the next, and the last before the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+impl<N: Debug> Error for CycleError<N> {}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "precedence rules form a cycle: ")?;
+        for node in &self.cycle {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Precedence<N> {
+    /// The nodes each node has to come before.
+    after: FastMap<N, FastSet<N>>,
+    /// The nodes each node has to come after.
+    before: FastMap<N, FastSet<N>>,
+    rules: usize,
+}
+
+impl<N> Default for Precedence<N> {
+    fn default() -> Self {
+        Precedence {
+            after: FastMap::default(),
+            before: FastMap::default(),
+            rules: 0,
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Precedence<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `before` to come before `after`. Returns whether the rule is new.
+    pub fn insert(&mut self, before: N, after: N) -> bool {
+        let inserted = self
+            .after
+            .entry(before.clone())
+            .or_default()
+            .insert(after.clone());
+        if inserted {
+            self.before.entry(after).or_default().insert(before);
+            self.rules += 1;
+        }
+        inserted
+    }
+
+    /// The number of rules.
+    pub fn len(&self) -> usize {
+        self.rules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules == 0
+    }
+
+    /// Whether a rule requires `before` to come before `after`.
+    pub fn requires(&self, before: &N, after: &N) -> bool {
+        self.after
+            .get(before)
+            .is_some_and(|set| set.contains(after))
+    }
+
+    /// The nodes that a rule requires to come after `node`.
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.after.get(node).into_iter().flatten()
+    }
+
+    /// The nodes that a rule requires to come before `node`.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.before.get(node).into_iter().flatten()
+    }
+
+    /// How `a` and `b` are ordered by a rule between them, `None` without one.
+    /// Rules in both directions count as `Less`, [`Precedence::toposort`] reports those as cycles.
+    pub fn compare(&self, a: &N, b: &N) -> Option<Ordering> {
+        if a == b {
+            Some(Ordering::Equal)
+        } else if self.requires(a, b) {
+            Some(Ordering::Less)
+        } else if self.requires(b, a) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// Whether no rule requires a node of `sequence` to come before a node in front of it.
+    pub fn is_ordered(&self, sequence: &[N]) -> bool {
+        sequence.iter().enumerate().all(|(i, node)| {
+            self.before
+                .get(node)
+                .is_none_or(|before| sequence[i + 1..].iter().all(|n| !before.contains(n)))
+        })
+    }
+
+    /// Order `nodes` so that every rule between two of them is respected, ignoring duplicates.
+    /// Nodes that are not ordered by any rule keep the order they have in `nodes`.
+    pub fn toposort(&self, nodes: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let mut index = FastMap::default();
+        let mut unique = vec![];
+        for node in nodes {
+            index.entry(node).or_insert_with(|| {
+                unique.push(node);
+                unique.len() - 1
+            });
+        }
+
+        // the rules between nodes of the subset, found from whichever side has fewer nodes.
+        let successors = unique
+            .iter()
+            .map(|node| match self.after.get(*node) {
+                None => vec![],
+                Some(after) if after.len() < unique.len() => {
+                    after.iter().filter_map(|n| index.get(n).copied()).collect()
+                }
+                Some(after) => (0..unique.len())
+                    .filter(|&j| after.contains(unique[j]))
+                    .collect(),
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut in_degree = vec![0; unique.len()];
+        for &j in successors.iter().flatten() {
+            in_degree[j] += 1;
+        }
+        let mut ready = (0..unique.len())
+            .filter(|&i| in_degree[i] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+
+        let mut sorted = Vec::with_capacity(unique.len());
+        while let Some(Reverse(i)) = ready.pop() {
+            sorted.push(unique[i].clone());
+            for &j in &successors[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    ready.push(Reverse(j));
+                }
+            }
+        }
+
+        if sorted.len() == unique.len() {
+            return Ok(sorted);
+        }
+
+        // every node left has a predecessor that is left too, so walking back along them has to loop.
+        let mut predecessor = vec![None; unique.len()];
+        for (i, after) in successors.iter().enumerate() {
+            for &j in after {
+                if in_degree[i] > 0 {
+                    predecessor[j] = Some(i);
+                }
+            }
+        }
+
+        let mut i = in_degree.iter().position(|&d| d > 0).unwrap();
+        let mut position = vec![None; unique.len()];
+        let mut path = vec![];
+        while position[i].is_none() {
+            position[i] = Some(path.len());
+            path.push(unique[i].clone());
+            i = predecessor[i].unwrap();
+        }
+
+        let mut cycle = path.split_off(position[i].unwrap());
+        cycle.reverse();
+        Err(CycleError { cycle })
+    }
+
+    /// A comparator for sorting `nodes` that respects every rule between them, if they do not form a cycle.
+    /// Nodes outside of `nodes` compare equal to each other and after all others.
+    pub fn comparator(&self, nodes: &[N]) -> Result<impl Fn(&N, &N) -> Ordering, CycleError<N>> {
+        let rank = self
+            .toposort(nodes)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| (node, i))
+            .collect::<FastMap<_, _>>();
+        Ok(move |a: &N, b: &N| {
+            let rank_of = |node| rank.get(node).copied().unwrap_or(usize::MAX);
+            rank_of(a).cmp(&rank_of(b))
+        })
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Precedence<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        let mut precedence = Precedence::new();
+        precedence.extend(iter);
+        precedence
+    }
+}
+
+impl<N: Clone + Eq + Hash> Extend<(N, N)> for Precedence<N> {
+    fn extend<I: IntoIterator<Item = (N, N)>>(&mut self, iter: I) {
+        for (before, after) in iter {
+            self.insert(before, after);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{CycleError, Precedence};
+
+    // the rules of day 5's example.
+    static RULES: &str = "47|53 97|13 97|61 97|47 75|29 61|13 75|53 29|13 97|29 53|29 61|53 \
+        97|53 61|29 47|13 75|47 97|75 47|61 75|61 47|29 75|13 53|13";
+
+    fn pages() -> Precedence<u32> {
+        RULES
+            .split_whitespace()
+            .map(|rule| {
+                let (before, after) = rule.split_once('|').unwrap();
+                (before.parse().unwrap(), after.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checks_orders() {
+        let mut rules = pages();
+        assert_eq!(rules.len(), 21);
+        assert!(!rules.insert(47, 53));
+        assert!(rules.requires(&47, &53));
+        assert!(!rules.requires(&53, &47));
+        assert_eq!(rules.compare(&53, &47), Some(Ordering::Greater));
+        assert_eq!(rules.compare(&53, &53), Some(Ordering::Equal));
+        assert_eq!(rules.compare(&53, &1), None);
+
+        assert!(rules.is_ordered(&[75, 47, 61, 53, 29]));
+        assert!(rules.is_ordered(&[75, 29, 13]));
+        assert!(!rules.is_ordered(&[75, 97, 47, 61, 53]));
+        assert!(!rules.is_ordered(&[61, 13, 29]));
+        assert!(rules.is_ordered(&[]));
+    }
+
+    #[test]
+    fn sorts_subsets() {
+        let rules = pages();
+        assert_eq!(
+            rules.toposort(&[75, 97, 47, 61, 53]),
+            Ok(vec![97, 75, 47, 61, 53])
+        );
+        assert_eq!(rules.toposort(&[61, 13, 29, 61]), Ok(vec![61, 29, 13]));
+        // unrelated nodes keep their order.
+        assert_eq!(rules.toposort(&[5, 13, 4, 29]), Ok(vec![5, 4, 29, 13]));
+
+        let mut update = vec![97, 13, 75, 29, 47];
+        let compare = rules.comparator(&update).unwrap();
+        update.sort_by(&compare);
+        assert_eq!(update, vec![97, 75, 47, 29, 13]);
+        assert_eq!(compare(&13, &1000), Ordering::Less);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let mut rules = pages();
+        rules.insert(13, 75);
+        // 75 comes before 13, so a subset with both contains a cycle through them.
+        let error = rules.toposort(&[13, 29, 75, 97]).unwrap_err();
+        assert!(error.cycle.contains(&13) && error.cycle.contains(&75));
+        for (i, node) in error.cycle.iter().enumerate() {
+            let next = &error.cycle[(i + 1) % error.cycle.len()];
+            assert!(rules.requires(node, next));
+        }
+        assert!(rules.comparator(&[75, 13]).is_err());
+        // the rest of the rules are still fine.
+        assert!(rules.toposort(&[29, 97, 75]).is_ok());
+
+        // the walk back from 5 enters the cycle at 2 and leaves out the tail.
+        let rules = [(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]
+            .into_iter()
+            .collect::<Precedence<_>>();
+        let error = rules.toposort(&[5, 4, 3, 2, 1]).unwrap_err();
+        assert_eq!(error.cycle, vec![2, 3, 4]);
+
+        let error = CycleError {
+            cycle: vec![1, 2, 3],
+        };
+        assert_eq!(
+            error.to_string(),
+            "precedence rules form a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+}
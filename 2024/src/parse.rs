@@ -0,0 +1,431 @@
+//! Helpers for parsing the usual shapes of puzzle inputs: integers scattered through text,
+//! blank-line separated blocks, delimited lists and `key: value` lines.
+//!
+//! Solutions can take an [`Input`] instead of a `&str`, the `solution!` macro converts the input file:
+//!
+//! ```ignore
+//! pub fn part_one(input: Input) -> Option<i64> {
+//!     let machines = input.blocks().map(|block| block.ints::<i64, 2>()).collect::<Result<Vec<_>, _>>().ok()?;
+//!     ...
+//! }
+//! ```
+//!
+//! Errors carry the (1-based) line number of the input they occurred on, also for lines of a block.
+
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use crate::{
+    grid::{Grid, TryParseGridError},
+    tile::Tile,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The puzzle input, or a block of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    text: &'a str,
+    /// Line number of the first line of `text` in the puzzle input.
+    first_line: usize,
+}
+
+/// A single line of the input, which knows its line number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    text: &'a str,
+    number: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Input {
+            text,
+            first_line: 1,
+        }
+    }
+
+    pub fn as_str(self) -> &'a str {
+        self.text
+    }
+
+    /// All lines, including empty ones.
+    pub fn lines(self) -> impl Iterator<Item = Line<'a>> {
+        self.text.lines().enumerate().map(move |(i, text)| Line {
+            text,
+            number: self.first_line + i,
+        })
+    }
+
+    /// Split into blocks separated by one or more blank lines.
+    pub fn blocks(self) -> impl Iterator<Item = Input<'a>> {
+        let mut blocks = vec![];
+        let mut start: Option<(usize, usize)> = None;
+        let mut offset = 0;
+
+        for (i, raw) in self.text.split_inclusive('\n').enumerate() {
+            let is_blank = raw.trim().is_empty();
+            match (start, is_blank) {
+                (None, false) => start = Some((offset, self.first_line + i)),
+                (Some((block_start, first_line)), true) => {
+                    blocks.push(Input {
+                        text: &self.text[block_start..offset],
+                        first_line,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+            offset += raw.len();
+        }
+
+        if let Some((block_start, first_line)) = start {
+            blocks.push(Input {
+                text: &self.text[block_start..],
+                first_line,
+            });
+        }
+
+        blocks.into_iter()
+    }
+
+    /// Every integer of the input, see [`Line::all_ints`].
+    pub fn all_ints<T: FromStr>(self) -> Result<Vec<T>, ParseError> {
+        let mut ints = vec![];
+        for line in self.lines() {
+            ints.extend(line.all_ints()?);
+        }
+        Ok(ints)
+    }
+
+    /// Exactly `N` integers of every non-empty line, see [`Line::ints`].
+    pub fn ints<T: FromStr, const N: usize>(self) -> Result<Vec<[T; N]>, ParseError> {
+        self.non_empty_lines().map(|line| line.ints()).collect()
+    }
+
+    /// The values of every non-empty line, split by `delimiter`, see [`Line::list`].
+    pub fn lists<T: FromStr>(self, delimiter: &str) -> Result<Vec<Vec<T>>, ParseError> {
+        self.non_empty_lines()
+            .map(|line| line.list(delimiter))
+            .collect()
+    }
+
+    /// Parse every non-empty line with `f`.
+    pub fn parse_lines<T>(
+        self,
+        f: impl FnMut(Line<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.non_empty_lines().map(f).collect()
+    }
+
+    /// A `key: value` pair per non-empty line, split at the first `separator`. Keys must be unique.
+    pub fn key_values(self, separator: &str) -> Result<HashMap<&'a str, &'a str>, ParseError> {
+        let mut map = HashMap::new();
+        for line in self.non_empty_lines() {
+            let (key, value) = line.key_value(separator)?;
+            if map.insert(key, value).is_some() {
+                return Err(line.error(format!("duplicate key `{key}`")));
+            }
+        }
+        Ok(map)
+    }
+
+    /// Parse a [`Grid`] from the input, mapping every character with `f`.
+    pub fn grid<T>(self, f: impl FnMut(char) -> T) -> Result<Grid<T>, ParseError> {
+        Grid::parse(self.text, f).map_err(|e| ParseError {
+            line: self.first_line + e.row,
+            message: e.to_string(),
+        })
+    }
+
+    /// Parse a [`Grid`] of [`Tile`]s from the input, e.g. an enum declared with [`crate::tiles!`].
+    pub fn tiles<T: Tile>(self) -> Result<Grid<T>, ParseError> {
+        Grid::parse_tiles(self.text).map_err(|e| match e {
+            TryParseGridError::Shape(e) => ParseError {
+                line: self.first_line + e.row,
+                message: e.to_string(),
+            },
+            TryParseGridError::Cell { x, y, error } => ParseError {
+                line: self.first_line + y,
+                message: format!("column {}: {error}", x + 1),
+            },
+        })
+    }
+
+    fn non_empty_lines(self) -> impl Iterator<Item = Line<'a>> {
+        self.lines().filter(|line| !line.text.trim().is_empty())
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(text: &'a str) -> Self {
+        Input::new(text)
+    }
+}
+
+impl Display for Input<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl<'a> Line<'a> {
+    pub fn as_str(self) -> &'a str {
+        self.text
+    }
+
+    /// The 1-based line number in the puzzle input.
+    pub fn number(self) -> usize {
+        self.number
+    }
+
+    /// An error located at this line.
+    pub fn error(self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            message: message.into(),
+        }
+    }
+
+    /// Parse the whole line, ignoring surrounding whitespace.
+    pub fn parse<T: FromStr>(self) -> Result<T, ParseError> {
+        parse_value(self, self.text.trim())
+    }
+
+    /// Every integer of the line, ignoring any text around them, e.g. `[94, -34]` for `Button A: X+94, Y-34`.
+    /// A `-` is a sign when it directly precedes a digit and does not follow one, so `1-2` yields `[1, 2]`.
+    pub fn all_ints<T: FromStr>(self) -> Result<Vec<T>, ParseError> {
+        int_spans(self.text)
+            .map(|span| parse_value(self, span))
+            .collect()
+    }
+
+    /// Exactly `N` integers of the line, see [`Line::all_ints`]. Destructures like a tuple:
+    /// `let [x, y] = line.ints::<i64, 2>()?;`.
+    pub fn ints<T: FromStr, const N: usize>(self) -> Result<[T; N], ParseError> {
+        let ints = self.all_ints::<T>()?;
+        let found = ints.len();
+        ints.try_into().map_err(|_| {
+            self.error(format!(
+                "expected {N} integers, found {found} in `{}`",
+                self.text
+            ))
+        })
+    }
+
+    /// The values of the line split by `delimiter`, each trimmed. A blank line is an empty list.
+    pub fn list<T: FromStr>(self, delimiter: &str) -> Result<Vec<T>, ParseError> {
+        if self.text.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        self.text
+            .split(delimiter)
+            .map(|value| parse_value(self, value.trim()))
+            .collect()
+    }
+
+    /// The values of the line split by any amount of whitespace, e.g. columns.
+    pub fn fields<T: FromStr>(self) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split_whitespace()
+            .map(|value| parse_value(self, value))
+            .collect()
+    }
+
+    /// Split the line at the first `separator` into a trimmed key and value.
+    pub fn key_value(self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(separator)
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| {
+                self.error(format!(
+                    "expected `key{separator}value`, found `{}`",
+                    self.text
+                ))
+            })
+    }
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+fn parse_value<T: FromStr>(line: Line, value: &str) -> Result<T, ParseError> {
+    value
+        .parse()
+        .map_err(|_| line.error(format!("could not parse `{value}`")))
+}
+
+/// The substrings of `s` that form (optionally negative) integers.
+fn int_spans(s: &str) -> impl Iterator<Item = &str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let is_sign = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+
+            if is_sign || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(&s[start..i]);
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Input, ParseError};
+
+    static MACHINES: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+";
+
+    #[test]
+    fn extracts_integers() {
+        let input = Input::new("p=0,4 v=3,-3\nx1-2 -5 --6 a-7");
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0].all_ints::<i32>().unwrap(), vec![0, 4, 3, -3]);
+        assert_eq!(lines[1].all_ints::<i32>().unwrap(), vec![1, 2, -5, -6, -7]);
+        assert_eq!(input.all_ints::<i64>().unwrap().len(), 9);
+
+        let error = lines[0].all_ints::<u8>().unwrap_err();
+        assert_eq!(error.to_string(), "line 1: could not parse `-3`");
+    }
+
+    #[test]
+    fn parses_fixed_arity() {
+        let [px, py, vx, vy] = Input::new("p=0,4 v=3,-3")
+            .lines()
+            .next()
+            .unwrap()
+            .ints::<i32, 4>()
+            .unwrap();
+        assert_eq!((px, py, vx, vy), (0, 4, 3, -3));
+
+        let error = Input::new("1 2\n3").ints::<u32, 2>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                message: "expected 2 integers, found 1 in `3`".into()
+            }
+        );
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = Input::new(MACHINES);
+        let blocks = input.blocks().collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 2);
+
+        let machines = blocks
+            .iter()
+            .map(|block| block.ints::<i64, 2>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(machines[1], vec![[26, 66], [67, 21], [12748, 12176]]);
+
+        // line numbers refer to the whole input.
+        assert_eq!(blocks[1].lines().next().unwrap().number(), 5);
+        assert_eq!(
+            Input::new("\n\na\n\n\n\nb\r\nc")
+                .blocks()
+                .map(|b| b.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a\n", "b\r\nc"]
+        );
+    }
+
+    #[test]
+    fn parses_lists() {
+        let input = Input::new("47|53\n97|13\n\n75,47,61\n97,61");
+        let [rules, updates] = input.blocks().collect::<Vec<_>>().try_into().unwrap();
+        assert_eq!(rules.ints::<u32, 2>().unwrap(), vec![[47, 53], [97, 13]]);
+        assert_eq!(
+            updates.lists::<u32>(",").unwrap(),
+            vec![vec![75, 47, 61], vec![97, 61]]
+        );
+
+        let error = Input::new("1,2\n3,x").lists::<u32>(",").unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn parses_columns() {
+        let columns = Input::new("3   4\n4   3\n")
+            .parse_lines(|line| line.fields::<u32>())
+            .unwrap();
+        assert_eq!(columns, vec![vec![3, 4], vec![4, 3]]);
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let input = Input::new("Register A: 729\nRegister B: 0\n\nProgram: 0,1,5,4");
+        let map = input.key_values(":").unwrap();
+        assert_eq!(map["Register A"], "729");
+        assert_eq!(map["Program"], "0,1,5,4");
+
+        let line = input.lines().next().unwrap();
+        assert_eq!(line.key_value(":").unwrap().1, "729");
+        assert_eq!(Input::new("a: 1\nb").key_values(":").unwrap_err().line, 2);
+        assert!(Input::new("a: 1\na: 2").key_values(":").is_err());
+    }
+
+    #[test]
+    fn parses_grids() {
+        let block = Input::new("x\n\n.#\n#.").blocks().nth(1).unwrap();
+        assert_eq!(block.grid(|c| c == '#').unwrap().width(), 2);
+        let error = Input::new("x\n\n.#\n#")
+            .blocks()
+            .nth(1)
+            .unwrap()
+            .grid(|c| c)
+            .unwrap_err();
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn parses_tiles() {
+        crate::tiles! {
+            enum Cell {
+                Open = '.',
+                Wall = '#',
+            }
+        }
+
+        let block = Input::new("x\n\n.#\n#.").blocks().nth(1).unwrap();
+        assert_eq!(block.tiles::<Cell>().unwrap()[(1, 0)], Cell::Wall);
+        let error = Input::new("..\n.@").tiles::<Cell>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: column 2: '@' is not a tile of Cell, expected one of \".#\""
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! A two-dimensional point or vector, generic over the integer type of its coordinates.
+//!
+//! Like [`crate::grid::Grid`], `x` is the column and `y` is the row, growing downwards.
+//! "Clockwise" therefore means clockwise as the grid is printed, e.g. up turns to the right.
+
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Add<Output = T>> Point<T> {
+    /// The number of orthogonal steps between `self` and `other`.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The number of orthogonal or diagonal steps between `self` and `other`.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Neg<Output = T>> Point<T> {
+    /// Rotate by 90° clockwise around the origin.
+    #[must_use]
+    pub fn rotate_cw(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotate by 90° counter-clockwise around the origin.
+    #[must_use]
+    pub fn rotate_ccw(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl<T: TryInto<usize>> Point<T> {
+    /// Convert to unsigned `(x, y)` grid coordinates. Returns `None` if a coordinate is negative or does not fit.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: TryFrom<usize>> Point<T> {
+    /// Convert from unsigned `(x, y)` grid coordinates. Returns `None` if a coordinate does not fit into `T`.
+    pub fn from_index((x, y): (usize, usize)) -> Option<Self> {
+        Some(Point::new(x.try_into().ok()?, y.try_into().ok()?))
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Point<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Point;
+    use crate::grid::Grid;
+
+    #[test]
+    fn applies_operators() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 4);
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Point::new(2, -6));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        c *= 2;
+        assert_eq!(c, Point::new(6, 2));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1_i64, 5);
+        let b = Point::new(4, -1);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+
+        // unsigned coordinates must not underflow.
+        let a = Point::new(2_u32, 7);
+        let b = Point::new(5, 3);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(b.manhattan(a), 7);
+    }
+
+    #[test]
+    fn rotates() {
+        let up = Point::new(0, -1);
+        assert_eq!(up.rotate_cw(), Point::new(1, 0));
+        assert_eq!(up.rotate_ccw(), Point::new(-1, 0));
+        assert_eq!(up.rotate_cw().rotate_cw(), -up);
+        assert_eq!(Point::new(2, 3).rotate_cw().rotate_ccw(), Point::new(2, 3));
+    }
+
+    #[test]
+    fn converts_tuples_and_indices() {
+        let point: Point<i32> = (3, 4).into();
+        assert_eq!(<(i32, i32)>::from(point), (3, 4));
+        assert_eq!(point.to_index(), Some((3, 4)));
+        assert_eq!(Point::new(-1, 4).to_index(), None);
+        assert_eq!(Point::<i8>::from_index((1, 2)), Some(Point::new(1, 2)));
+        assert_eq!(Point::<i8>::from_index((1000, 2)), None);
+        assert_eq!(Point::new(1, -2).to_string(), "(1, -2)");
+    }
+
+    #[test]
+    fn indexes_grids() {
+        let mut grid = Grid::parse("ab\ncd", |c| c).unwrap();
+        assert_eq!(grid.get_point(Point::new(1_i32, 1)), Some(&'d'));
+        assert_eq!(grid.get_point(Point::new(-1_i32, 1)), None);
+        assert_eq!(grid.get_point(Point::new(2_u8, 0)), None);
+        assert_eq!(grid[Point::new(1_i64, 0)], 'b');
+
+        grid[Point::new(0_usize, 1)] = 'x';
+        *grid.get_point_mut(Point::new(0_i16, 0)).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "yb\nxd");
+    }
+}
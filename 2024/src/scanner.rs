@@ -0,0 +1,294 @@
+//! A cursor over bytes for puzzles that hide tokens in noisy text, e.g. `mul(2,4)` in day 3.
+//!
+//! Every operation either consumes what it matched or leaves the position untouched, so patterns
+//! can be chained with `?` and alternatives tried one after another. [`Scanner::find_all`] runs such a
+//! pattern at every position of the input without allocating.
+//!
+//! ```
+//! use advent_of_code::scanner::Scanner;
+//!
+//! let products = Scanner::new(b"xmul(2,4)%&mul[3,7]mul(11,8)").find_all(|s| {
+//!     s.literal(b"mul(")?;
+//!     let a = s.uint::<u32>(3)?;
+//!     s.expect(b',')?;
+//!     let b = s.uint::<u32>(3)?;
+//!     s.expect(b')')?;
+//!     Some(a * b)
+//! });
+//! assert_eq!(products.sum::<u32>(), 96);
+//! ```
+
+/// A position in a byte string, see the [module documentation](self).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Scanner { bytes, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move to `position`, e.g. to backtrack to a position saved earlier.
+    pub fn seek(&mut self, position: usize) {
+        self.position = position.min(self.bytes.len());
+    }
+
+    /// The bytes that have not been consumed yet.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    /// Consume and return the next byte.
+    pub fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    /// Skip up to `n` bytes.
+    pub fn skip(&mut self, n: usize) {
+        self.seek(self.position + n);
+    }
+
+    /// Consume `literal` if the input continues with it.
+    pub fn literal(&mut self, literal: &[u8]) -> Option<()> {
+        // checking the first byte up front rejects most positions of `find_all` without a `memcmp`.
+        let first_matches = literal.first().is_none_or(|b| self.peek() == Some(*b));
+        if first_matches && self.rest().starts_with(literal) {
+            self.position += literal.len();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Consume `byte` if it is the next byte.
+    pub fn expect(&mut self, byte: u8) -> Option<()> {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Read an unsigned integer of 1 to `max_digits` digits. Stops after `max_digits` digits, even if more follow.
+    /// Fails without consuming anything if there is no digit or the value does not fit into `T`.
+    pub fn uint<T: TryFrom<u64>>(&mut self, max_digits: usize) -> Option<T> {
+        let start = self.position;
+        let mut value: u64 = 0;
+
+        while self.position - start < max_digits {
+            match self.peek() {
+                Some(byte @ b'0'..=b'9') => {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add(u64::from(byte - b'0')))
+                        .or_else(|| {
+                            self.position = start;
+                            None
+                        })?;
+                    self.position += 1;
+                }
+                _ => break,
+            }
+        }
+
+        if self.position == start {
+            return None;
+        }
+        T::try_from(value).ok().or_else(|| {
+            self.position = start;
+            None
+        })
+    }
+
+    /// Read an integer of 1 to `max_digits` digits with an optional `-` or `+` sign, see [`Scanner::uint`].
+    pub fn int<T: TryFrom<i64>>(&mut self, max_digits: usize) -> Option<T> {
+        self.attempt(|s| {
+            let negative = s.expect(b'-').is_some();
+            if !negative {
+                s.expect(b'+');
+            }
+            let magnitude = i64::try_from(s.uint::<u64>(max_digits)?).ok()?;
+            T::try_from(if negative { -magnitude } else { magnitude }).ok()
+        })
+    }
+
+    /// Run `pattern`, restoring the position if it fails.
+    pub fn attempt<T>(&mut self, pattern: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let start = self.position;
+        let result = pattern(self);
+        if result.is_none() {
+            self.position = start;
+        }
+        result
+    }
+
+    /// Advance to the next occurrence of `byte`, without consuming it. Moves to the end if there is none.
+    pub fn skip_to(&mut self, byte: u8) -> bool {
+        match self.rest().iter().position(|b| *b == byte) {
+            Some(offset) => {
+                self.position += offset;
+                true
+            }
+            None => {
+                self.position = self.bytes.len();
+                false
+            }
+        }
+    }
+
+    /// Every match of `pattern` in the rest of the input, trying it at each position in turn.
+    /// After a match, scanning continues behind it; after a failure, one byte further.
+    pub fn find_all<T, F>(self, pattern: F) -> FindAll<'a, F>
+    where
+        F: FnMut(&mut Scanner<'a>) -> Option<T>,
+    {
+        FindAll {
+            scanner: self,
+            pattern,
+        }
+    }
+}
+
+/// The iterator returned by [`Scanner::find_all`].
+pub struct FindAll<'a, F> {
+    scanner: Scanner<'a>,
+    pattern: F,
+}
+
+impl<'a, T, F> Iterator for FindAll<'a, F>
+where
+    F: FnMut(&mut Scanner<'a>) -> Option<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.scanner.is_at_end() {
+            let start = self.scanner.position;
+            if let Some(found) = (self.pattern)(&mut self.scanner) {
+                // a pattern that matches nothing must not stall the iterator.
+                if self.scanner.position == start {
+                    self.scanner.position += 1;
+                }
+                return Some(found);
+            }
+            self.scanner.position = start + 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scanner;
+
+    #[derive(Debug, PartialEq)]
+    enum Instruction {
+        Mul(u32, u32),
+        Do,
+        Dont,
+    }
+
+    fn instruction(s: &mut Scanner) -> Option<Instruction> {
+        if s.literal(b"mul(").is_some() {
+            let a = s.uint(3)?;
+            s.expect(b',')?;
+            let b = s.uint(3)?;
+            s.expect(b')')?;
+            Some(Instruction::Mul(a, b))
+        } else if s.literal(b"do()").is_some() {
+            Some(Instruction::Do)
+        } else {
+            s.literal(b"don't()").map(|_| Instruction::Dont)
+        }
+    }
+
+    #[test]
+    fn reads_integers() {
+        let mut s = Scanner::new(b"1234,-56,+7,-x,99999");
+        assert_eq!(s.uint::<u32>(3), Some(123));
+        assert_eq!(s.uint::<u32>(3), Some(4));
+        assert_eq!(s.uint::<u32>(3), None);
+        s.expect(b',').unwrap();
+        assert_eq!(s.uint::<u32>(3), None);
+        assert_eq!(s.int::<i32>(3), Some(-56));
+        s.skip(1);
+        assert_eq!(s.int::<i8>(1), Some(7));
+        s.skip(1);
+
+        // failures leave the position untouched.
+        let position = s.position();
+        assert_eq!(s.int::<i32>(3), None);
+        assert_eq!(s.position(), position);
+        s.skip(3);
+        assert_eq!(s.uint::<u16>(5), None);
+        assert_eq!(s.rest(), b"99999");
+        assert_eq!(s.uint::<u32>(5), Some(99999));
+        assert!(s.is_at_end());
+    }
+
+    #[test]
+    fn matches_literals() {
+        let mut s = Scanner::new(b"don't()");
+        assert_eq!(s.literal(b"do()"), None);
+        assert_eq!(s.position(), 0);
+        assert_eq!(
+            s.attempt(|s| s.literal(b"do").and_then(|_| s.expect(b'x'))),
+            None
+        );
+        assert_eq!(s.position(), 0);
+        assert_eq!(s.literal(b"don't"), Some(()));
+        assert_eq!(s.bump(), Some(b'('));
+        assert!(s.skip_to(b')'));
+        assert!(!s.skip_to(b'x'));
+        assert!(s.is_at_end());
+    }
+
+    #[test]
+    fn finds_all_occurrences() {
+        let input = b"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let sum = Scanner::new(input)
+            .find_all(instruction)
+            .map(|i| match i {
+                Instruction::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum::<u32>();
+        assert_eq!(sum, 161);
+
+        let input = b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let instructions = Scanner::new(input)
+            .find_all(instruction)
+            .collect::<Vec<_>>();
+        assert_eq!(instructions[1], Instruction::Dont);
+        let (sum, _) = instructions
+            .iter()
+            .fold((0, true), |(sum, enabled), i| match i {
+                Instruction::Mul(a, b) if enabled => (sum + a * b, enabled),
+                Instruction::Mul(..) => (sum, enabled),
+                Instruction::Do => (sum, true),
+                Instruction::Dont => (sum, false),
+            });
+        assert_eq!(sum, 48);
+
+        // overlapping candidates: the first `mul(` fails and scanning resumes right after its start.
+        let found = Scanner::new(b"mul(mul(1,2)").find_all(instruction).count();
+        assert_eq!(found, 1);
+    }
+}
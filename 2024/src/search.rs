@@ -0,0 +1,499 @@
+//! Graph searches over implicit graphs: nodes are any hashable state, edges come from a successor closure.
+//!
+//! [`bfs`], [`dijkstra`] and [`astar`] explore from one or more start nodes until the cheapest goal is reached,
+//! or until the graph is exhausted if no node is a goal. They return [`Paths`], which keeps every predecessor
+//! on a shortest path, so besides one shortest path it can also list all shortest paths, count them, or
+//! collect all nodes that lie on any of them.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::hash::{FastMap, FastSet};
+
+/// The outcome of a search: the cost of every reached node and its predecessors on shortest paths.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    distances: FastMap<N, C>,
+    predecessors: FastMap<N, Vec<N>>,
+    goals: Vec<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    /// The cost of the cheapest path to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// All reached nodes with their costs. The search stops once nothing is left that could reach a goal
+    /// as cheaply, so this covers every node up to the cost of the cheapest goal, and also some further
+    /// ones: the successors of the last nodes explored are recorded before the search stops.
+    pub fn distances(&self) -> &FastMap<N, C> {
+        &self.distances
+    }
+
+    /// The first goal the search reached.
+    pub fn goal(&self) -> Option<&N> {
+        self.goals.first()
+    }
+
+    /// All goals that were reached at the cost of the cheapest goal.
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// The cost of the cheapest goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goal().and_then(|goal| self.distance(goal))
+    }
+
+    /// The direct predecessors of `node` on shortest paths. Empty for start nodes and unreached nodes.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from a start node to `node`, including both.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from a start node to `node`. The number of paths can grow exponentially,
+    /// prefer [`Paths::count_paths_to`] or [`Paths::nodes_on_paths_to`] if the paths themselves are not needed.
+    pub fn all_paths_to(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return vec![];
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![vec![node.clone()]];
+
+        while let Some(path) = stack.pop() {
+            let predecessors = self.predecessors(path.last().unwrap());
+            if predecessors.is_empty() {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+            for previous in predecessors {
+                let mut next = path.clone();
+                next.push(previous.clone());
+                stack.push(next);
+            }
+        }
+
+        paths
+    }
+
+    /// The number of shortest paths from a start node to `node`. Nodes are counted in order of their
+    /// distance without recursing, so long paths are fine, but edges of cost zero are not supported.
+    pub fn count_paths_to(&self, node: &N) -> u64
+    where
+        C: Ord,
+    {
+        if !self.distances.contains_key(node) {
+            return 0;
+        }
+
+        let mut nodes = vec![node];
+        let mut seen = FastSet::default();
+        seen.insert(node);
+        let mut i = 0;
+        while let Some(current) = nodes.get(i) {
+            for previous in self.predecessors(current) {
+                if seen.insert(previous) {
+                    nodes.push(previous);
+                }
+            }
+            i += 1;
+        }
+
+        // every predecessor is closer than its successors, so its count is known by the time it is needed.
+        nodes.sort_unstable_by_key(|n| self.distances[*n]);
+        let mut counts: FastMap<&N, u64> =
+            FastMap::with_capacity_and_hasher(nodes.len(), Default::default());
+        for n in nodes {
+            let predecessors = self.predecessors(n);
+            let count = if predecessors.is_empty() {
+                1
+            } else {
+                predecessors.iter().map(|p| counts[p]).sum()
+            };
+            counts.insert(n, count);
+        }
+        counts[node]
+    }
+
+    /// All nodes that lie on any shortest path from a start node to one of `targets`, including both ends.
+    pub fn nodes_on_paths_to<'a>(&self, targets: impl IntoIterator<Item = &'a N>) -> FastSet<N>
+    where
+        N: 'a,
+    {
+        let mut nodes = FastSet::default();
+        let mut stack: Vec<&N> = targets
+            .into_iter()
+            .filter(|n| self.distances.contains_key(n))
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.predecessors(node));
+            }
+        }
+
+        nodes
+    }
+}
+
+/// Breadth-first search where every edge has a cost of one. A successor that is returned more than once
+/// for the same node is counted as one edge.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths {
+        distances: FastMap::default(),
+        predecessors: FastMap::default(),
+        goals: vec![],
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !paths.distances.contains_key(&start) {
+            paths.distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        // all goals at the cheapest distance are queued before any node further away.
+        if let Some(goal_distance) = paths.cost() {
+            if distance > goal_distance {
+                break;
+            }
+        }
+
+        if is_goal(&node) {
+            paths.goals.push(node.clone());
+            continue;
+        }
+
+        for next in successors(&node) {
+            match paths.distances.get(&next) {
+                Some(d) if *d < distance + 1 => {}
+                Some(_) => {
+                    let predecessors = paths.predecessors.entry(next).or_default();
+                    if !predecessors.contains(&node) {
+                        predecessors.push(node.clone());
+                    }
+                }
+                None => {
+                    paths.distances.insert(next.clone(), distance + 1);
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// Iterative depth-first search, returning every node reachable from `start` in the order it was first visited.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    let mut visited = FastSet::default();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        // push in reverse, so successors are visited in the order they were returned.
+        stack.extend(
+            successors(&node)
+                .into_iter()
+                .rev()
+                .filter(|n| !visited.contains(n)),
+        );
+        order.push(node);
+    }
+
+    order
+}
+
+/// Dijkstra's algorithm. Costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search. The `heuristic` must never overestimate the remaining cost to a goal. For all shortest paths
+/// to be found, it also has to be consistent, i.e. never decrease by more than the cost of an edge.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        distances: FastMap::default(),
+        predecessors: FastMap::default(),
+        goals: vec![],
+    };
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if !paths.distances.contains_key(&start) {
+            paths.distances.insert(start.clone(), C::default());
+            heap.push(Entry {
+                priority: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(Entry {
+        priority,
+        cost,
+        node,
+    }) = heap.pop()
+    {
+        // keep going until nothing cheaper than the goal is left, to find every goal and predecessor at that cost.
+        if let Some(goal_cost) = paths.cost() {
+            if priority > goal_cost {
+                break;
+            }
+        }
+
+        // skip entries that were superseded by a cheaper path.
+        if paths.distances.get(&node).is_some_and(|d| cost > *d) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            paths.goals.push(node);
+            continue;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            match paths.distances.get(&next) {
+                Some(d) if *d < next_cost => {}
+                Some(d) if *d == next_cost => {
+                    let predecessors = paths.predecessors.entry(next).or_default();
+                    if !predecessors.contains(&node) {
+                        predecessors.push(node.clone());
+                    }
+                }
+                _ => {
+                    paths.distances.insert(next.clone(), next_cost);
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    heap.push(Entry {
+                        priority: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// A node in the priority queue, ordered so the [`BinaryHeap`] pops the lowest priority first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // prefer deeper nodes on equal priority, which reaches goals sooner with A*.
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dfs, dijkstra};
+    use crate::{direction::Dir4, grid::Grid, hash::FastSet, point::Point};
+
+    fn grid_successors(grid: &Grid<char>, p: &Point<isize>) -> Vec<Point<isize>> {
+        Dir4::iter()
+            .map(|d| *p + d)
+            .filter(|n| grid.get_point(*n).is_some_and(|c| *c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let grid = Grid::parse("S..\n.#.\n..E", |c| c).unwrap();
+        let start = Point::new(0, 0);
+        let end = Point::new(2, 2);
+        let paths = bfs([start], |p| grid_successors(&grid, p), |p| *p == end);
+
+        assert_eq!(paths.cost(), Some(4));
+        assert_eq!(paths.goal(), Some(&end));
+        assert_eq!(paths.path_to(&end).unwrap().len(), 5);
+        assert_eq!(paths.count_paths_to(&end), 2);
+        assert_eq!(paths.all_paths_to(&end).len(), 2);
+        // every open cell lies on one of the two paths around the wall.
+        assert_eq!(paths.nodes_on_paths_to([&end]).len(), 8);
+        assert_eq!(paths.predecessors(&start), &[]);
+    }
+
+    #[test]
+    fn explores_everything_without_goal() {
+        let grid = Grid::parse("..#\n#..\n###", |c| c).unwrap();
+        let paths = bfs([Point::new(0, 0)], |p| grid_successors(&grid, p), |_| false);
+        assert_eq!(paths.goal(), None);
+        assert_eq!(paths.distances().len(), 4);
+        assert_eq!(paths.distance(&Point::new(2, 1)), Some(3));
+        assert_eq!(paths.distance(&Point::new(2, 0)), None);
+        assert_eq!(paths.count_paths_to(&Point::new(2, 0)), 0);
+    }
+
+    #[test]
+    fn supports_multiple_starts() {
+        let paths = bfs([0_i32, 10], |n| [n - 1, n + 1], |n| *n == 7);
+        assert_eq!(paths.cost(), Some(3));
+        assert_eq!(paths.path_to(&7), Some(vec![10, 9, 8, 7]));
+        // -3 and 13 are as far as the goal and still record their successors.
+        assert_eq!(paths.distance(&14), Some(4));
+        assert_eq!(paths.distance(&15), None);
+    }
+
+    #[test]
+    fn counts_each_edge_once() {
+        // every node yields its successor twice, which must not double the count.
+        let paths = bfs([0_u32], |n| [n + 1, n + 1], |n| *n == 3);
+        assert_eq!(paths.predecessors(&2), &[1]);
+        assert_eq!(paths.count_paths_to(&3), 1);
+    }
+
+    #[test]
+    fn counts_paths_along_long_paths() {
+        let paths = bfs([0_u32], |n| [n + 1], |n| *n == 100_000);
+        assert_eq!(paths.count_paths_to(&100_000), 1);
+    }
+
+    #[test]
+    fn visits_depth_first() {
+        // a binary tree with nodes `1..8`.
+        let order = dfs(1, |n| {
+            [n * 2, n * 2 + 1]
+                .into_iter()
+                .filter(|c| *c < 8)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(order, vec![1, 2, 4, 5, 3, 6, 7]);
+    }
+
+    /// A reindeer maze as in day 16: moving forward costs 1, turning costs 1000.
+    fn maze_successors(
+        grid: &Grid<char>,
+        (p, d): &(Point<isize>, Dir4),
+    ) -> Vec<((Point<isize>, Dir4), u32)> {
+        let mut next = vec![((*p, d.turn_left()), 1000), ((*p, d.turn_right()), 1000)];
+        if grid.get_point(*p + *d).is_some_and(|c| *c != '#') {
+            next.push(((*p + *d, *d), 1));
+        }
+        next
+    }
+
+    #[test]
+    fn finds_all_nodes_on_shortest_paths() {
+        let grid = Grid::parse("#######\n#.....#\n#S.#.E#\n#.....#\n#######", |c| c).unwrap();
+        let start = (grid.find(|c| *c == 'S').unwrap(), Dir4::Right);
+        let start = (Point::from_index(start.0).unwrap(), start.1);
+        let end: Point<isize> = Point::from_index(grid.find(|c| *c == 'E').unwrap()).unwrap();
+
+        let paths = dijkstra([start], |s| maze_successors(&grid, s), |(p, _)| *p == end);
+        assert_eq!(paths.cost(), Some(3006));
+        assert_eq!(paths.goals().len(), 2);
+        assert_eq!(
+            paths
+                .goals()
+                .iter()
+                .map(|g| paths.count_paths_to(g))
+                .sum::<u64>(),
+            4
+        );
+
+        let tiles = paths
+            .nodes_on_paths_to(paths.goals())
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect::<FastSet<_>>();
+        // around the wall above or below, turning in the first or second column.
+        assert_eq!(tiles.len(), 13);
+    }
+
+    #[test]
+    fn finds_the_same_cost_with_astar() {
+        let grid = Grid::parse("S....\n.###.\n.#...\n.#.#.\n...#E", |c| c).unwrap();
+        let start = Point::new(0, 0);
+        let end = Point::new(4, 4);
+        let successors = |p: &Point<isize>| grid_successors(&grid, p).into_iter().map(|n| (n, 1));
+
+        let expected = dijkstra([start], successors, |p| *p == end);
+        let paths = astar([start], successors, |p| p.manhattan(end), |p| *p == end);
+        assert_eq!(paths.cost(), Some(8));
+        assert_eq!(paths.cost(), expected.cost());
+        assert_eq!(paths.count_paths_to(&end), expected.count_paths_to(&end));
+        assert!(paths.distances().len() <= expected.distances().len());
+    }
+}
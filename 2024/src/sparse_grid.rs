@@ -0,0 +1,354 @@
+//! A grid without fixed bounds that only stores occupied cells, e.g. for robots moving around or a map
+//! growing in every direction.
+//!
+//! Coordinates are signed [`Point`]s, with `x` as the column and `y` as the row like in [`crate::grid::Grid`].
+//! Besides the cells, the grid keeps how many cells are occupied in every row and column, and the
+//! bounding box. The box grows with every insert and only has to be searched again when the last cell
+//! of its outermost row or column is removed.
+
+use std::{
+    collections::hash_map,
+    fmt::Display,
+    ops::{Index, RangeInclusive},
+};
+
+use crate::{hash::FastMap, point::Point};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: FastMap<Point<i64>, T>,
+    /// The number of occupied cells per row.
+    rows: Histogram,
+    /// The number of occupied cells per column.
+    columns: Histogram,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: FastMap::default(),
+            rows: Histogram::default(),
+            columns: Histogram::default(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point<i64>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point<i64>) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Set the cell at `point`, returning its previous value.
+    pub fn insert(&mut self, point: Point<i64>, value: T) -> Option<T> {
+        let previous = self.cells.insert(point, value);
+        if previous.is_none() {
+            self.rows.increment(point.y);
+            self.columns.increment(point.x);
+        }
+        previous
+    }
+
+    /// Clear the cell at `point`, returning its value.
+    pub fn remove(&mut self, point: Point<i64>) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+        self.rows.decrement(point.y);
+        self.columns.decrement(point.x);
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.rows.clear();
+        self.columns.clear();
+    }
+
+    /// All occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    /// The top left and bottom right corner of the smallest box around all occupied cells.
+    pub fn bounds(&self) -> Option<(Point<i64>, Point<i64>)> {
+        let (x, y) = (self.x_range()?, self.y_range()?);
+        Some((
+            Point::new(*x.start(), *y.start()),
+            Point::new(*x.end(), *y.end()),
+        ))
+    }
+
+    /// The columns from the leftmost to the rightmost occupied cell.
+    pub fn x_range(&self) -> Option<RangeInclusive<i64>> {
+        self.columns.range()
+    }
+
+    /// The rows from the topmost to the bottommost occupied cell.
+    pub fn y_range(&self) -> Option<RangeInclusive<i64>> {
+        self.rows.range()
+    }
+
+    /// The number of occupied cells in row `y`.
+    pub fn row_count(&self, y: i64) -> usize {
+        self.rows.get(y)
+    }
+
+    /// The number of occupied cells in column `x`.
+    pub fn column_count(&self, x: i64) -> usize {
+        self.columns.get(x)
+    }
+
+    /// Every row with occupied cells and their number, in no particular order.
+    pub fn row_counts(&self) -> impl Iterator<Item = (i64, usize)> + '_ {
+        self.rows.iter()
+    }
+
+    /// Every column with occupied cells and their number, in no particular order.
+    pub fn column_counts(&self) -> impl Iterator<Item = (i64, usize)> + '_ {
+        self.columns.iter()
+    }
+
+    /// Render the bounding box with `f`, which gets the value of every cell or `None` if it is empty.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+        let mut out = String::new();
+        for y in min.y..=max.y {
+            if y > min.y {
+                out.push('\n');
+            }
+            for x in min.x..=max.x {
+                out.push(f(self.get(Point::new(x, y))));
+            }
+        }
+        out
+    }
+}
+
+/// Counting grids, where every cell holds the number of entities on it.
+impl SparseGrid<usize> {
+    /// Add an entity at `point`. Returns the number of entities on it now.
+    pub fn add(&mut self, point: Point<i64>) -> usize {
+        match self.cells.entry(point) {
+            hash_map::Entry::Occupied(mut entry) => {
+                *entry.get_mut() += 1;
+                *entry.get()
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(1);
+                self.rows.increment(point.y);
+                self.columns.increment(point.x);
+                1
+            }
+        }
+    }
+
+    /// Take an entity from `point`, clearing the cell when it was the last one.
+    /// Returns the number of entities left on it.
+    pub fn subtract(&mut self, point: Point<i64>) -> usize {
+        match self.cells.entry(point) {
+            hash_map::Entry::Occupied(mut entry) if *entry.get() > 1 => {
+                *entry.get_mut() -= 1;
+                *entry.get()
+            }
+            hash_map::Entry::Occupied(entry) => {
+                entry.remove();
+                self.rows.decrement(point.y);
+                self.columns.decrement(point.x);
+                0
+            }
+            hash_map::Entry::Vacant(_) => 0,
+        }
+    }
+
+    /// The number of entities in the grid.
+    pub fn total(&self) -> usize {
+        self.cells.values().sum()
+    }
+}
+
+/// How many cells are occupied per row or column, and the range of the occupied ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Histogram {
+    counts: FastMap<i64, usize>,
+    range: Option<(i64, i64)>,
+}
+
+impl Histogram {
+    fn get(&self, key: i64) -> usize {
+        self.counts.get(&key).copied().unwrap_or(0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (i64, usize)> + '_ {
+        self.counts.iter().map(|(key, count)| (*key, *count))
+    }
+
+    fn range(&self) -> Option<RangeInclusive<i64>> {
+        self.range.map(|(min, max)| min..=max)
+    }
+
+    fn increment(&mut self, key: i64) {
+        *self.counts.entry(key).or_insert(0) += 1;
+        self.range = Some(match self.range {
+            Some((min, max)) => (min.min(key), max.max(key)),
+            None => (key, key),
+        });
+    }
+
+    fn decrement(&mut self, key: i64) {
+        if let hash_map::Entry::Occupied(mut entry) = self.counts.entry(key) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+                if self
+                    .range
+                    .is_some_and(|(min, max)| key == min || key == max)
+                {
+                    let keys = self.counts.keys();
+                    self.range = keys
+                        .clone()
+                        .min()
+                        .zip(keys.max())
+                        .map(|(min, max)| (*min, *max));
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.counts.clear();
+        self.range = None;
+    }
+}
+
+impl<T> Index<Point<i64>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<i64>) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is not occupied"))
+    }
+}
+
+impl<T> FromIterator<(Point<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point<i64>, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+/// Renders the bounding box row by row, with empty cells as `.`.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+            for x in min.x..=max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(value) => write!(f, "{value}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseGrid;
+    use crate::point::Point;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point::new(2, 3), 'a');
+        grid.insert(Point::new(-4, 1), 'b');
+        grid.insert(Point::new(0, -5), 'c');
+        assert_eq!(grid.insert(Point::new(2, 3), 'd'), Some('a'));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some((Point::new(-4, -5), Point::new(2, 3))));
+
+        assert_eq!(grid.remove(Point::new(-4, 1)), Some('b'));
+        assert_eq!(grid.remove(Point::new(-4, 1)), None);
+        assert_eq!(grid.x_range(), Some(0..=2));
+        assert_eq!(grid.y_range(), Some(-5..=3));
+        assert_eq!(grid[Point::new(2, 3)], 'd');
+
+        grid.clear();
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn counts_entities() {
+        let mut grid = SparseGrid::new();
+        for (x, y) in [(0, 0), (1, 0), (1, 0), (3, 0), (1, 2)] {
+            grid.add(Point::new(x, y));
+        }
+        assert_eq!(grid[Point::new(1, 0)], 2);
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid.total(), 5);
+        assert_eq!(grid.row_count(0), 3);
+        assert_eq!(grid.column_count(1), 2);
+        let mut rows = grid.row_counts().collect::<Vec<_>>();
+        rows.sort();
+        assert_eq!(rows, vec![(0, 3), (2, 1)]);
+        let mut columns = grid.column_counts().collect::<Vec<_>>();
+        columns.sort();
+        assert_eq!(columns, vec![(0, 1), (1, 2), (3, 1)]);
+
+        assert_eq!(grid.subtract(Point::new(1, 0)), 1);
+        assert_eq!(grid.subtract(Point::new(1, 2)), 0);
+        assert_eq!(grid.subtract(Point::new(9, 9)), 0);
+        assert_eq!(grid.row_count(2), 0);
+        assert_eq!(grid.y_range(), Some(0..=0));
+    }
+
+    #[test]
+    fn renders() {
+        let grid = [(Point::new(-1, 0), 1), (Point::new(2, 1), 3)]
+            .into_iter()
+            .collect::<SparseGrid<usize>>();
+        assert_eq!(grid.to_string(), "1...\n...3");
+        assert_eq!(
+            grid.render(|cell| if cell.is_some() { '#' } else { ' ' }),
+            "#   \n   #"
+        );
+        assert_eq!(SparseGrid::<char>::new().to_string(), "");
+    }
+}
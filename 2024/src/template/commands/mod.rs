@@ -1,6 +1,8 @@
 pub mod all;
 pub mod download;
+pub mod progress;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
@@ -0,0 +1,33 @@
+use std::process;
+
+use crate::template::readme::{self, begin_marker, end_marker, progress::ProgressTable};
+use crate::template::submissions::Submissions;
+
+pub fn handle() {
+    let submissions = Submissions::read_from_file();
+
+    match readme::update(&[&ProgressTable::new(&submissions)]) {
+        Ok(rendered) if rendered.updated.contains(&"progress") => {
+            println!(
+                "Stored updated progress ({} stars).",
+                submissions.total_stars()
+            );
+        }
+        Ok(_) if submissions.data.is_empty() => {
+            eprintln!("Failed to store updated progress: no submissions recorded yet.");
+            process::exit(1);
+        }
+        Ok(_) => {
+            eprintln!(
+                "Failed to store updated progress: add a `{}` / `{}` pair to README.md.",
+                begin_marker("progress"),
+                end_marker("progress")
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to store updated progress: {e}");
+            process::exit(1);
+        }
+    }
+}
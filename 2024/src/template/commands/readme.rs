@@ -0,0 +1,57 @@
+use std::process;
+
+use crate::template::fingerprint::Fingerprint;
+use crate::template::readme::{
+    self, benchmarks::Benchmarks, charts::Charts, notes::Notes, progress::ProgressTable, Generator,
+};
+use crate::template::submissions::Submissions;
+use crate::template::timings::TimingsStore;
+
+pub fn handle(check: bool) {
+    let fingerprint = Fingerprint::current("release", &[]);
+    let machine =
+        match readme::benchmarks::select_machine(&TimingsStore::read_from_file(), &fingerprint) {
+            Ok(machine) => machine,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+    let benchmarks = Benchmarks {
+        machine: machine.clone(),
+    };
+    let charts = Charts { machine };
+    let progress = ProgressTable::new(&Submissions::read_from_file());
+    let notes = Notes::read_from_files();
+    let generators: [&dyn Generator; 4] = [&benchmarks, &charts, &progress, &notes];
+
+    let result = if check {
+        readme::check(&generators)
+    } else {
+        readme::update(&generators)
+    };
+
+    match result {
+        Ok(rendered) if check && !rendered.stale.is_empty() => {
+            eprintln!(
+                "README.md is stale, sections out of date: {}. Run `cargo readme` to update.",
+                rendered.stale.join(", ")
+            );
+            process::exit(1);
+        }
+        Ok(_) if check => {
+            println!("README.md is up to date.");
+        }
+        Ok(rendered) if rendered.stale.is_empty() => {
+            println!("README.md is already up to date.");
+        }
+        Ok(rendered) => {
+            println!("Updated sections: {}.", rendered.stale.join(", "));
+        }
+        Err(e) => {
+            eprintln!("Failed to update README.md: {e}");
+            process::exit(1);
+        }
+    }
+}
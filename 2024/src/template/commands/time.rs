@@ -1,18 +1,30 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::export::Format;
+use crate::template::fingerprint::Fingerprint;
+use crate::template::matrix::{self, DayComparison, Matrix};
+use crate::template::readme::{
+    self, begin_marker, benchmarks::Benchmarks, charts::Charts, end_marker,
+};
+use crate::template::run_multi::{child_commands, run_multi};
+use crate::template::timings::TimingsStore;
+use crate::template::{all_days, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, format: Format, redact: bool) {
+    let fingerprint = Fingerprint::current("release", &[]);
+    let stored = TimingsStore::read_from_file();
+    let stored_timings = stored
+        .get(&fingerprint)
+        .map(|m| m.timings.clone())
+        .unwrap_or_default();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched on this machine.
                 all_days()
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
@@ -21,20 +33,117 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, format, redact).unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged = stored.merge(&fingerprint, &timings);
+        merged.store_file().unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
+        let machine = match readme::benchmarks::select_machine(&merged, &fingerprint) {
+            Ok(machine) => machine,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let benchmarks = Benchmarks {
+            machine: machine.clone(),
+        };
+        let charts = Charts { machine };
+
+        // keep stdout machine-readable for formats other than the table.
+        match readme::update(&[&benchmarks, &charts]) {
+            Ok(rendered) if !rendered.updated.contains(&"benchmarks") => {
+                eprintln!(
+                    "Failed to store updated benchmarks: add a `{}` / `{}` pair to README.md.",
+                    begin_marker("benchmarks"),
+                    end_marker("benchmarks")
+                );
+            }
+            Ok(_) if format == Format::Table => {
+                println!();
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Ok(_) => {
+                eprintln!("Stored updated benchmarks.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
 }
+
+/// Print every machine that has stored timings, so one can be picked for the readme.
+pub fn handle_machines() {
+    let stored = TimingsStore::read_from_file();
+    let current = Fingerprint::current("release", &[]);
+
+    if stored.machines.is_empty() {
+        println!("No stored benchmarks.");
+        return;
+    }
+
+    for machine in &stored.machines {
+        let marker = if machine.fingerprint == current {
+            " (this machine)"
+        } else {
+            ""
+        };
+        println!(
+            "{}: {} [{} days]{marker}",
+            machine.fingerprint.id(),
+            machine.fingerprint,
+            machine.timings.data.len()
+        );
+    }
+}
+
+/// Build and benchmark solutions with every variant of the matrix, then print a comparison table.
+pub fn handle_matrix(day: Option<Day>) {
+    let matrix = match Matrix::read_from_file() {
+        Ok(matrix) => matrix,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Day> = all_days()
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .collect();
+
+    for variant in &matrix.variants {
+        let fingerprint = Fingerprint::current(&variant.profile, &variant.features());
+        eprintln!("{}: {fingerprint}", variant.name);
+    }
+
+    let comparisons: Vec<DayComparison> = days
+        .into_iter()
+        .filter_map(|day| {
+            let timings: Vec<_> = matrix
+                .variants
+                .iter()
+                .map(|variant| {
+                    eprintln!("Benchmarking day {day} with `{}`...", variant.name);
+                    let output = child_commands::run_variant(day, variant, false).unwrap();
+                    (!output.is_empty()).then(|| child_commands::parse_exec_time(&output, day))
+                })
+                .collect();
+
+            timings
+                .iter()
+                .any(Option::is_some)
+                .then_some(DayComparison { day, timings })
+        })
+        .collect();
+
+    if comparisons.is_empty() {
+        println!("No solutions to benchmark.");
+        return;
+    }
+
+    println!();
+    println!("{}", matrix::construct_table(&matrix, &comparisons));
+}
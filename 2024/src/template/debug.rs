@@ -0,0 +1,106 @@
+//! Debug frames: images a solution part writes while it runs, e.g. one per step of a simulation.
+//!
+//! Run a solution with `cargo solve <day> --debug-frames` and call [`frame`] from a part. Frames of the
+//! first run of each part are written to `data/debug/<day>/part<part>-<frame>.png`. Runs for benchmarks
+//! never record, and without the flag the image is not even built, so parts can call [`frame`] unconditionally.
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{image::Image, template::Day};
+
+struct Recording {
+    dir: PathBuf,
+    part: u8,
+    frames: usize,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// Whether frames are recorded right now.
+pub fn is_recording() -> bool {
+    RECORDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Record the frame built by `image`, which is only called while recording.
+pub fn frame(image: impl FnOnce() -> Image) {
+    let path = {
+        let recording = RECORDING.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(recording) = recording.as_ref() else {
+            return;
+        };
+        recording.dir.join(format!(
+            "part{}-{:05}.png",
+            recording.part, recording.frames
+        ))
+    };
+
+    let saved = image().save(&path);
+
+    let mut recording = RECORDING.lock().unwrap_or_else(PoisonError::into_inner);
+    match saved {
+        Ok(()) => {
+            if let Some(recording) = recording.as_mut() {
+                recording.frames += 1;
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write debug frame {}: {e}", path.display());
+            *recording = None;
+        }
+    }
+}
+
+/// Start recording the frames of `part` if the solution runs with `--debug-frames`.
+/// Frames of the part left over from an earlier run are removed.
+pub(crate) fn start(day: Day, part: u8) {
+    if !env::args().any(|x| x == "--debug-frames") {
+        return;
+    }
+
+    let dir = env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("debug")
+        .join(day.to_string());
+    let prefix = format!("part{part}-");
+    let prepared = fs::create_dir_all(&dir).and_then(|()| {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let is_frame = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix));
+            if is_frame {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    });
+
+    if let Err(e) = prepared {
+        eprintln!("Failed to prepare {}: {e}", dir.display());
+        return;
+    }
+
+    *RECORDING.lock().unwrap_or_else(PoisonError::into_inner) = Some(Recording {
+        dir,
+        part,
+        frames: 0,
+    });
+}
+
+/// Stop recording. Returns the number of frames written and their directory, if recording.
+pub(crate) fn stop() -> Option<(usize, PathBuf)> {
+    RECORDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+        .map(|recording| (recording.frames, recording.dir))
+}
@@ -0,0 +1,285 @@
+//! Module that renders solution results in machine-readable formats, for use with spreadsheets and dashboards.
+
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static REDACTED: &str = "<redacted>";
+
+/// The output format of commands that run multiple solutions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable output, streamed while the solutions run.
+    #[default]
+    Table,
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `table`, `csv`, `json` or `markdown`")
+    }
+}
+
+/// Represents the outcome of running one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub time: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+}
+
+/// Represents the outcome of running a day's solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayResult {
+    pub day: Day,
+    pub parts: Vec<PartResult>,
+}
+
+/// Render results in the given format. Returns `None` for [`Format::Table`], which is streamed instead.
+pub fn render(format: Format, results: &[DayResult], redact: bool) -> Option<String> {
+    match format {
+        Format::Table => None,
+        Format::Csv => Some(to_csv(results, redact)),
+        Format::Json => Some(to_json(results, redact)),
+        Format::Markdown => Some(to_markdown(results, redact)),
+    }
+}
+
+fn answer_of(part: &PartResult, redact: bool) -> Option<String> {
+    part.answer.as_ref().map(|answer| {
+        if redact {
+            REDACTED.into()
+        } else {
+            answer.clone()
+        }
+    })
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn to_csv(results: &[DayResult], redact: bool) -> String {
+    let mut lines = vec!["day,part,answer,time,nanos,samples".to_string()];
+
+    for result in results {
+        for part in &result.parts {
+            lines.push(format!(
+                "{},{},{},{},{},{}",
+                result.day,
+                part.part,
+                escape_csv(&answer_of(part, redact).unwrap_or_default()),
+                part.time.as_deref().unwrap_or_default(),
+                part.nanos,
+                part.samples
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn to_json(results: &[DayResult], redact: bool) -> String {
+    let days = results
+        .iter()
+        .map(|result| {
+            let parts = result
+                .parts
+                .iter()
+                .map(|part| {
+                    let mut map: HashMap<String, JsonValue> = HashMap::new();
+                    map.insert("part".into(), JsonValue::Number(f64::from(part.part)));
+                    map.insert(
+                        "answer".into(),
+                        answer_of(part, redact).map_or(JsonValue::Null, JsonValue::String),
+                    );
+                    map.insert(
+                        "time".into(),
+                        part.time.clone().map_or(JsonValue::Null, JsonValue::String),
+                    );
+                    map.insert("nanos".into(), JsonValue::Number(part.nanos));
+                    #[allow(clippy::cast_precision_loss)]
+                    map.insert("samples".into(), JsonValue::Number(part.samples as f64));
+                    JsonValue::Object(map)
+                })
+                .collect();
+
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(result.day.to_string()));
+            map.insert("parts".into(), JsonValue::Array(parts));
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let total_nanos = results
+        .iter()
+        .flat_map(|r| r.parts.iter())
+        .map(|p| p.nanos)
+        .sum();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("days".into(), JsonValue::Array(days));
+    map.insert("total_nanos".into(), JsonValue::Number(total_nanos));
+
+    // NOTE: serializing a `JsonValue` built from strings and numbers can not fail.
+    JsonValue::Object(map).format().unwrap()
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn to_markdown(results: &[DayResult], redact: bool) -> String {
+    let mut lines = vec![
+        "| Day | Part | Answer | Time | Samples |".to_string(),
+        "| :---: | :---: | :--- | :---: | :---: |".to_string(),
+    ];
+
+    for result in results {
+        for part in &result.parts {
+            lines.push(format!(
+                "| [Day {}](./src/bin/{}.rs) | {} | {} | {} | {} |",
+                result.day.into_inner(),
+                result.day,
+                part.part,
+                answer_of(part, redact).map_or("-".into(), |a| escape_markdown(&a)),
+                part.time
+                    .as_ref()
+                    .map_or("-".into(), |time| format!("`{time}`")),
+                part.samples
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, DayResult, Format, PartResult};
+    use crate::day;
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    fn get_mock_results() -> Vec<DayResult> {
+        vec![
+            DayResult {
+                day: day!(1),
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Some("42".into()),
+                        time: Some("1.5ms".into()),
+                        nanos: 1_500_000_f64,
+                        samples: 667,
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: None,
+                        time: None,
+                        nanos: 0_f64,
+                        samples: 0,
+                    },
+                ],
+            },
+            DayResult {
+                day: day!(2),
+                parts: vec![PartResult {
+                    part: 1,
+                    answer: Some("a,\"b\"".into()),
+                    time: Some("10.0ns".into()),
+                    nanos: 10_f64,
+                    samples: 10000,
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("csv").unwrap(), Format::Csv);
+        assert_eq!(Format::from_str("markdown").unwrap(), Format::Markdown);
+        assert!(Format::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn streams_tables() {
+        assert_eq!(render(Format::Table, &get_mock_results(), false), None);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(Format::Csv, &get_mock_results(), false).unwrap();
+        let expected = [
+            "day,part,answer,time,nanos,samples",
+            "01,1,42,1.5ms,1500000,667",
+            "01,2,,,0,0",
+            "02,1,\"a,\"\"b\"\"\",10.0ns,10,10000",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = render(Format::Markdown, &get_mock_results(), true).unwrap();
+        let expected = [
+            "| Day | Part | Answer | Time | Samples |",
+            "| :---: | :---: | :--- | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | 1 | <redacted> | `1.5ms` | 667 |",
+            "| [Day 1](./src/bin/01.rs) | 2 | - | - | 0 |",
+            "| [Day 2](./src/bin/02.rs) | 1 | <redacted> | `10.0ns` | 10000 |",
+        ]
+        .join("\n");
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(Format::Json, &get_mock_results(), false).unwrap();
+        let value = JsonValue::from_str(&json).unwrap();
+        let object = value.get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(object["total_nanos"], JsonValue::Number(1_500_010_f64));
+
+        let days = object["days"].get::<Vec<JsonValue>>().unwrap();
+        assert_eq!(days.len(), 2);
+
+        let parts = days[0].get::<HashMap<String, JsonValue>>().unwrap()["parts"]
+            .get::<Vec<JsonValue>>()
+            .unwrap();
+        let part = parts[0].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(part["answer"], JsonValue::String("42".into()));
+        assert_eq!(part["samples"], JsonValue::Number(667_f64));
+        assert!(parts[1].get::<HashMap<String, JsonValue>>().unwrap()["answer"].is_null());
+    }
+}
@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Describes the machine and toolchain a set of timings was recorded on.
+/// Timings are only comparable if their fingerprints are equal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub profile: String,
+    pub features: Vec<String>,
+}
+
+impl Fingerprint {
+    /// Fingerprint the current machine for solutions built with `profile` and `features`.
+    pub fn current(profile: &str, features: &[String]) -> Self {
+        let mut features = features.to_vec();
+        features.sort();
+
+        Fingerprint {
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(0, std::num::NonZero::get),
+            rustc: rustc_version().unwrap_or_else(|| "unknown".into()),
+            profile: profile.into(),
+            features,
+        }
+    }
+
+    /// A short identifier for this fingerprint, to refer to it from the command-line. The hash
+    /// function is fixed, unlike std hashers, so equal fingerprints get the same id in every run.
+    /// The id changes whenever any field does, e.g. after a toolchain update.
+    pub fn id(&self) -> String {
+        // FNV-1a, since std hashers make no stability guarantees.
+        let mut hash: u32 = 0x811c_9dc5;
+        let key = format!(
+            "{}\0{}\0{}\0{}\0{}",
+            self.cpu,
+            self.cores,
+            self.rustc,
+            self.profile,
+            self.features.join(",")
+        );
+        for byte in key.bytes() {
+            hash ^= u32::from(byte);
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        format!("{hash:08x}")
+    }
+
+    /// Whether this fingerprint carries no information, e.g. for timings stored by older versions.
+    pub fn is_unknown(&self) -> bool {
+        self == &Fingerprint::default()
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_unknown() {
+            return f.write_str("unknown machine");
+        }
+
+        write!(
+            f,
+            "{} ({} cores), {}, `{}` profile",
+            self.cpu, self.cores, self.rustc, self.profile
+        )?;
+
+        if !self.features.is_empty() {
+            write!(f, ", features: {}", self.features.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return parse_cpu_model(&cpuinfo);
+    }
+
+    // macOS does not have procfs.
+    let output = Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()?;
+    let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !model.is_empty()).then_some(model)
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    // x86 reports `model name`, some ARM kernels only report `Hardware` or `Model`.
+    ["model name", "Hardware", "Model"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == *key && !value.trim().is_empty())
+                .then(|| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+    })
+}
+
+fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !version.is_empty()).then_some(version)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fingerprint> for JsonValue {
+    fn from(value: &Fingerprint) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fingerprint {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fingerprint to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected fingerprint.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected fingerprint.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected fingerprint.features to be an array.")?
+            .iter()
+            .map(|v| v.get::<String>().cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Expected fingerprint.features to contain strings.")?;
+
+        Ok(Fingerprint {
+            cpu: get_string("cpu")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            cores: cores as usize,
+            rustc: get_string("rustc")?,
+            profile: get_string("profile")?,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cpu_model, Fingerprint};
+    use tinyjson::JsonValue;
+
+    fn get_mock_fingerprint() -> Fingerprint {
+        Fingerprint {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            cores: 16,
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            profile: "release".into(),
+            features: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7  5800X 8-Core Processor\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn ids_are_stable_and_distinct() {
+        let fingerprint = get_mock_fingerprint();
+        assert_eq!(fingerprint.id(), get_mock_fingerprint().id());
+        assert_eq!(fingerprint.id().len(), 8);
+
+        let other = Fingerprint {
+            profile: "dhat".into(),
+            ..get_mock_fingerprint()
+        };
+        assert_ne!(fingerprint.id(), other.id());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let fingerprint = Fingerprint {
+            features: vec!["dhat-heap".into()],
+            ..get_mock_fingerprint()
+        };
+        let json = JsonValue::from(&fingerprint);
+        assert_eq!(Fingerprint::try_from(&json).unwrap(), fingerprint);
+    }
+
+    #[test]
+    fn displays_fingerprint() {
+        assert_eq!(
+            get_mock_fingerprint().to_string(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 cores), rustc 1.83.0 (90b35a623 2024-11-26), `release` profile"
+        );
+        assert_eq!(Fingerprint::default().to_string(), "unknown machine");
+    }
+}
@@ -0,0 +1,364 @@
+//! Module that compares solution timings across build profiles and codegen settings.
+//!
+//! Variants are read from `data/matrix.json`, falling back to [`Matrix::default`] if the file is not present:
+//!
+//! ```json
+//! { "variants": [{ "name": "native", "profile": "release", "rustflags": "-C target-cpu=native" }] }
+//! ```
+
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{format_nanos, Timing};
+use crate::template::Day;
+
+static MATRIX_FILE_PATH: &str = "./data/matrix.json";
+
+/// A build configuration to benchmark solutions with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    /// The cargo profile to build with, see `[profile.*]` in `Cargo.toml`.
+    pub profile: String,
+    /// Overrides `RUSTFLAGS` if set.
+    pub rustflags: Option<String>,
+}
+
+impl Variant {
+    /// Each variant builds into its own target directory, so switching between
+    /// `RUSTFLAGS` does not invalidate the build cache of other variants.
+    pub fn target_dir(&self) -> String {
+        format!("target/matrix/{}", self.name)
+    }
+
+    /// Features recorded in the fingerprint of timings measured with this variant.
+    pub fn features(&self) -> Vec<String> {
+        self.rustflags
+            .iter()
+            .map(|rustflags| format!("rustflags={rustflags}"))
+            .collect()
+    }
+}
+
+/// The list of variants to benchmark.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    pub variants: Vec<Variant>,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        let variant = |name: &str, profile: &str, rustflags: Option<&str>| Variant {
+            name: name.into(),
+            profile: profile.into(),
+            rustflags: rustflags.map(String::from),
+        };
+
+        Matrix {
+            variants: vec![
+                variant("release", "release", None),
+                variant("lto", "release-lto", None),
+                variant("cgu1", "release-cgu1", None),
+                variant("abort", "release-abort", None),
+                variant("native", "release", Some("-C target-cpu=native")),
+            ],
+        }
+    }
+}
+
+impl Matrix {
+    /// Read the variants from `data/matrix.json`, or use the default variants if the file is not present.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(MATRIX_FILE_PATH) {
+            Ok(json) => Matrix::try_from(json)
+                .map_err(|e| format!("could not parse {MATRIX_FILE_PATH}: {e}")),
+            Err(_) => Ok(Matrix::default()),
+        }
+    }
+}
+
+/// The timings of every variant for a single day, in the order of the variants.
+pub struct DayComparison {
+    pub day: Day,
+    pub timings: Vec<Option<Timing>>,
+}
+
+impl DayComparison {
+    /// Index of the fastest variant. Variants that did not time both parts are not considered,
+    /// since their total is not comparable.
+    pub fn best(&self) -> Option<usize> {
+        self.timings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, timing)| {
+                let timing = timing.as_ref()?;
+                (timing.part_1.is_some() && timing.part_2.is_some())
+                    .then_some((i, timing.total_nanos))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Render a comparison table with a column per variant, marking the fastest variant of each day with `*`.
+/// The speedup of the fastest variant is relative to the first variant.
+pub fn construct_table(matrix: &Matrix, comparisons: &[DayComparison]) -> String {
+    let mut header: Vec<String> = vec!["Day".into()];
+    header.extend(matrix.variants.iter().map(|v| v.name.clone()));
+    header.push("Best".into());
+
+    let mut rows: Vec<Vec<String>> = vec![header];
+
+    for comparison in comparisons {
+        let best = comparison.best();
+        let mut row: Vec<String> = vec![comparison.day.to_string()];
+
+        row.extend(
+            comparison
+                .timings
+                .iter()
+                .enumerate()
+                .map(|(i, timing)| match timing {
+                    Some(timing) if best == Some(i) => {
+                        format!("{}*", format_nanos(timing.total_nanos))
+                    }
+                    Some(timing) => format_nanos(timing.total_nanos),
+                    None => "-".into(),
+                }),
+        );
+
+        let baseline = comparison.timings.first().and_then(Option::as_ref);
+        row.push(match best {
+            Some(i) => {
+                let name = &matrix.variants[i].name;
+                let fastest = comparison.timings[i].as_ref().map(|t| t.total_nanos);
+                match (baseline.map(|t| t.total_nanos), fastest) {
+                    (Some(base), Some(fastest)) if i > 0 && fastest > 0.0 => {
+                        format!("{name} ({:.2}x)", base / fastest)
+                    }
+                    _ => name.clone(),
+                }
+            }
+            None => "-".into(),
+        });
+
+        rows.push(row);
+    }
+
+    let columns = rows[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row.get(i).map_or(0, |cell| cell.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&rows[0])];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-|-"),
+    );
+    lines.extend(rows[1..].iter().map(|row| format_row(row)));
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Matrix {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let variants: Vec<Variant> = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("variants")
+            .ok_or("expected JSON document to have key `variants`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.variants` to be an array.")?
+            .iter()
+            .map(Variant::try_from)
+            .collect::<Result<_, _>>()?;
+
+        if variants.is_empty() {
+            return Err("expected `json.variants` to not be empty.".into());
+        }
+
+        for (i, variant) in variants.iter().enumerate() {
+            if variants[..i].iter().any(|v| v.name == variant.name) {
+                return Err(format!(
+                    "variant `{}` is defined more than once.",
+                    variant.name
+                ));
+            }
+        }
+
+        Ok(Matrix { variants })
+    }
+}
+
+impl TryFrom<&JsonValue> for Variant {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected variant to be a JSON object.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .filter(|name| {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            })
+            .ok_or(
+                "Expected variant.name to be a non-empty string of letters, digits, `-` or `_`.",
+            )?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected variant.profile to be a string.")?;
+
+        let rustflags = match json.get("rustflags") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected variant.rustflags to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        Ok(Variant {
+            name: name.clone(),
+            profile: profile.clone(),
+            rustflags,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, DayComparison, Matrix, Variant};
+    use crate::{day, template::timings::Timing};
+
+    fn timing(total_nanos: f64, complete: bool) -> Option<Timing> {
+        Some(Timing {
+            day: day!(1),
+            part_1: Some("-".into()),
+            part_2: complete.then(|| "-".into()),
+            total_nanos,
+        })
+    }
+
+    fn get_mock_matrix() -> Matrix {
+        Matrix::try_from(
+            r#"{ "variants": [
+                { "name": "release", "profile": "release" },
+                { "name": "native", "profile": "release", "rustflags": "-C target-cpu=native" }
+            ] }"#
+                .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_variants() {
+        let matrix = get_mock_matrix();
+        assert_eq!(matrix.variants.len(), 2);
+        assert_eq!(matrix.variants[0].rustflags, None);
+        assert_eq!(
+            matrix.variants[1],
+            Variant {
+                name: "native".into(),
+                profile: "release".into(),
+                rustflags: Some("-C target-cpu=native".into()),
+            }
+        );
+        assert_eq!(matrix.variants[1].target_dir(), "target/matrix/native");
+        assert_eq!(
+            matrix.variants[1].features(),
+            vec!["rustflags=-C target-cpu=native"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_variants() {
+        assert!(Matrix::try_from(r#"{ "variants": [] }"#.to_string()).is_err());
+        assert!(Matrix::try_from(
+            r#"{ "variants": [{ "name": "../x", "profile": "release" }] }"#.to_string()
+        )
+        .is_err());
+        assert!(Matrix::try_from(
+            r#"{ "variants": [{ "name": "a", "profile": "release" }, { "name": "a", "profile": "dev" }] }"#
+                .to_string()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn defaults_to_builtin_profiles() {
+        let matrix = Matrix::default();
+        assert_eq!(matrix.variants[0].profile, "release");
+        assert!(matrix.variants.iter().any(|v| v.profile == "release-lto"));
+    }
+
+    #[test]
+    fn picks_best_complete_variant() {
+        let comparison = DayComparison {
+            day: day!(1),
+            timings: vec![timing(2e6, true), timing(1e6, false), timing(1.5e6, true)],
+        };
+        assert_eq!(comparison.best(), Some(2));
+
+        let comparison = DayComparison {
+            day: day!(1),
+            timings: vec![None, timing(1e6, false)],
+        };
+        assert_eq!(comparison.best(), None);
+    }
+
+    #[test]
+    fn format_table() {
+        let comparisons = vec![
+            DayComparison {
+                day: day!(1),
+                timings: vec![timing(2e6, true), timing(1e6, true)],
+            },
+            DayComparison {
+                day: day!(2),
+                timings: vec![timing(500.0, true), None],
+            },
+        ];
+        let expected = [
+            "Day | release | native  | Best",
+            "----|---------|---------|---------------",
+            "01  | 2.00ms  | 1.00ms* | native (2.00x)",
+            "02  | 500ns*  | -       | release",
+        ]
+        .join("\n");
+        assert_eq!(construct_table(&get_mock_matrix(), &comparisons), expected);
+    }
+}
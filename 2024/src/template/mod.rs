@@ -2,13 +2,18 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod debug;
+pub mod export;
 pub mod runner;
 
 pub use day::*;
 
 mod day;
-mod readme_benchmarks;
+mod fingerprint;
+mod matrix;
+mod readme;
 mod run_multi;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
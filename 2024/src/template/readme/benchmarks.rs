@@ -0,0 +1,205 @@
+//! Renders the benchmarking table from stored timings.
+
+use std::env;
+
+use super::{get_path_for_bin, Generator};
+use crate::template::fingerprint::Fingerprint;
+use crate::template::timings::{MachineTimings, TimingsStore};
+
+/// Environment variable that selects the machine whose timings are shown in the readme.
+static README_MACHINE_ENV: &str = "AOC_README_MACHINE";
+
+pub struct Benchmarks {
+    pub machine: Option<MachineTimings>,
+}
+
+impl Generator for Benchmarks {
+    fn name(&self) -> &'static str {
+        "benchmarks"
+    }
+
+    fn render(&self) -> Option<String> {
+        self.machine
+            .as_ref()
+            .map(|machine| construct_table("##", machine))
+    }
+}
+
+/// Pick the timings shown in the readme: the machine selected via `AOC_README_MACHINE`,
+/// or the current machine if the variable is not set.
+pub fn select_machine(
+    stored: &TimingsStore,
+    current: &Fingerprint,
+) -> Result<Option<MachineTimings>, String> {
+    match env::var(README_MACHINE_ENV) {
+        Ok(id) if !id.is_empty() => stored.find(&id).cloned().map(Some).ok_or_else(|| {
+            format!(
+                "{README_MACHINE_ENV}=\"{id}\" does not match exactly one machine. Run `cargo time --machines` to list stored machines."
+            )
+        }),
+        _ => Ok(stored.get(current).cloned()),
+    }
+}
+
+fn construct_table(prefix: &str, machine: &MachineTimings) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---:  |".into(),
+    ];
+
+    for timing in &machine.timings.data {
+        let path = get_path_for_bin(timing.day);
+        lines.push(format!(
+            "| [Day {}]({}) | `{}` | `{}` |",
+            timing.day.into_inner(),
+            path,
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        ));
+    }
+
+    let total_millis = machine.timings.total_millis();
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    let fingerprint = &machine.fingerprint;
+    if !fingerprint.is_unknown() {
+        lines.push(String::new());
+        lines.push(format!(
+            "_Measured on {fingerprint} (machine `{}`)._",
+            fingerprint.id()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Benchmarks;
+    use crate::{
+        day,
+        template::fingerprint::Fingerprint,
+        template::readme::{begin_marker, end_marker, render},
+        template::timings::{MachineTimings, Timing, Timings},
+    };
+
+    fn get_mock_machine(fingerprint: Fingerprint) -> MachineTimings {
+        MachineTimings {
+            fingerprint,
+            timings: Timings {
+                data: vec![
+                    Timing {
+                        day: day!(1),
+                        part_1: Some("10ms".into()),
+                        part_2: Some("20ms".into()),
+                        total_nanos: 3e+7,
+                    },
+                    Timing {
+                        day: day!(2),
+                        part_1: Some("30ms".into()),
+                        part_2: Some("40ms".into()),
+                        total_nanos: 7e+7,
+                    },
+                    Timing {
+                        day: day!(4),
+                        part_1: Some("40ms".into()),
+                        part_2: Some("50ms".into()),
+                        total_nanos: 9e+7,
+                    },
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn skips_if_section_not_present() {
+        let benchmarks = Benchmarks {
+            machine: Some(get_mock_machine(Fingerprint::default())),
+        };
+        let rendered = render("# readme", &[&benchmarks]).unwrap();
+        assert!(rendered.updated.is_empty());
+    }
+
+    #[test]
+    fn skips_missing_timings() {
+        let readme = format!(
+            "{}\nfoo\n{}",
+            begin_marker("benchmarks"),
+            end_marker("benchmarks")
+        );
+        let rendered = render(&readme, &[&Benchmarks { machine: None }]).unwrap();
+        assert_eq!(rendered.readme, readme);
+    }
+
+    #[test]
+    fn updates_existing_benchmarks() {
+        let benchmarks = Benchmarks {
+            machine: Some(get_mock_machine(Fingerprint::default())),
+        };
+        let s = format!(
+            "foo\nbar\n{}{}\nbaz",
+            begin_marker("benchmarks"),
+            end_marker("benchmarks")
+        );
+        let s = render(&s, &[&benchmarks]).unwrap().readme;
+        let s = render(&s, &[&benchmarks]).unwrap().readme;
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn format_benchmarks() {
+        let benchmarks = Benchmarks {
+            machine: Some(get_mock_machine(Fingerprint::default())),
+        };
+        let s = format!(
+            "foo\nbar\n{}\n{}\nbaz",
+            begin_marker("benchmarks"),
+            end_marker("benchmarks")
+        );
+        let s = render(&s, &[&benchmarks]).unwrap().readme;
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- begin:benchmarks --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- end:benchmarks --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_fingerprint() {
+        let fingerprint = Fingerprint {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            features: vec![],
+        };
+        let benchmarks = Benchmarks {
+            machine: Some(get_mock_machine(fingerprint.clone())),
+        };
+        let s = format!("{}{}", begin_marker("benchmarks"), end_marker("benchmarks"));
+        let s = render(&s, &[&benchmarks]).unwrap().readme;
+        let expected_line = format!(
+            "_Measured on Apple M1 (8 cores), rustc 1.83.0, `release` profile (machine `{}`)._",
+            fingerprint.id()
+        );
+        assert!(s.contains(&format!("**Total: 190.00ms**\n\n{expected_line}\n")));
+    }
+}
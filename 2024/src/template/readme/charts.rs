@@ -0,0 +1,127 @@
+//! Renders a bar chart of the total time per day from stored timings.
+
+use super::Generator;
+use crate::template::timings::{format_nanos, MachineTimings};
+
+static BAR_WIDTH: f64 = 40.0;
+
+pub struct Charts {
+    pub machine: Option<MachineTimings>,
+}
+
+impl Generator for Charts {
+    fn name(&self) -> &'static str {
+        "charts"
+    }
+
+    fn render(&self) -> Option<String> {
+        let machine = self.machine.as_ref()?;
+        (!machine.timings.data.is_empty()).then(|| construct_chart("##", machine))
+    }
+}
+
+/// Bars are scaled logarithmically, since solutions range from nanoseconds to seconds.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar_length(nanos: f64, max_nanos: f64) -> usize {
+    // `ln_1p` keeps sub-nanosecond and zero timings at an empty bar.
+    let max = max_nanos.ln_1p();
+    if max <= 0.0 {
+        return 0;
+    }
+    (nanos.ln_1p() / max * BAR_WIDTH).round().max(0.0) as usize
+}
+
+fn construct_chart(prefix: &str, machine: &MachineTimings) -> String {
+    let data = &machine.timings.data;
+    let max_nanos = data.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    let mut lines: Vec<String> = vec![format!("{prefix} Charts"), String::new(), "```text".into()];
+
+    for timing in data {
+        lines.push(format!(
+            "Day {:>2} │{:<width$}│ {}",
+            timing.day.into_inner(),
+            "█".repeat(bar_length(timing.total_nanos, max_nanos)),
+            format_nanos(timing.total_nanos),
+            width = BAR_WIDTH as usize
+        ));
+    }
+
+    lines.push("```".into());
+    lines.push(String::new());
+    lines.push("_Bars are scaled logarithmically._".into());
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bar_length, Charts};
+    use crate::{
+        day,
+        template::fingerprint::Fingerprint,
+        template::readme::{begin_marker, end_marker, render},
+        template::timings::{MachineTimings, Timing, Timings},
+    };
+
+    #[test]
+    fn scales_bars() {
+        assert_eq!(bar_length(0.0, 1e9), 0);
+        assert_eq!(bar_length(1e9, 1e9), 40);
+        assert_eq!(bar_length(0.0, 0.0), 0);
+        assert!(bar_length(1e3, 1e9) < bar_length(1e6, 1e9));
+    }
+
+    #[test]
+    fn skips_empty_timings() {
+        let charts = Charts {
+            machine: Some(MachineTimings {
+                fingerprint: Fingerprint::default(),
+                timings: Timings::default(),
+            }),
+        };
+        let readme = format!("{}{}", begin_marker("charts"), end_marker("charts"));
+        assert!(render(&readme, &[&charts]).unwrap().updated.is_empty());
+    }
+
+    #[test]
+    fn format_charts() {
+        let charts = Charts {
+            machine: Some(MachineTimings {
+                fingerprint: Fingerprint::default(),
+                timings: Timings {
+                    data: vec![
+                        Timing {
+                            day: day!(1),
+                            part_1: Some("1ms".into()),
+                            part_2: None,
+                            total_nanos: 1e6,
+                        },
+                        Timing {
+                            day: day!(12),
+                            part_1: None,
+                            part_2: None,
+                            total_nanos: 0.0,
+                        },
+                    ],
+                },
+            }),
+        };
+        let readme = format!("{}{}", begin_marker("charts"), end_marker("charts"));
+        let s = render(&readme, &[&charts]).unwrap().readme;
+        let expected = [
+            "<!--- begin:charts --->",
+            "## Charts",
+            "",
+            "```text",
+            &format!("Day  1 │{}│ 1.00ms", "█".repeat(40)),
+            &format!("Day 12 │{}│ 0ns", " ".repeat(40)),
+            "```",
+            "",
+            "_Bars are scaled logarithmically._",
+            "<!--- end:charts --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,354 @@
+//! Module that keeps generated sections of the readme up to date.
+//!
+//! A section is delimited by a pair of named markers, everything outside of sections is left untouched:
+//!
+//! ```md
+//! <!--- begin:benchmarks --->
+//! ...generated content...
+//! <!--- end:benchmarks --->
+//! ```
+//!
+//! Each section is rendered by its own [`Generator`].
+
+use std::{fmt::Display, fs, io, ops::Range};
+
+use crate::template::Day;
+
+pub mod benchmarks;
+pub mod charts;
+pub mod notes;
+pub mod progress;
+
+static README_PATH: &str = "README.md";
+
+static MARKER_START: &str = "<!--- ";
+static MARKER_END: &str = " --->";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(message) => f.write_str(message),
+            Error::IO(e) => write!(f, "could not access {README_PATH}: {e}"),
+        }
+    }
+}
+
+/// Renders the content of one named readme section.
+pub trait Generator {
+    /// The name used in the section's markers.
+    fn name(&self) -> &'static str;
+
+    /// Render the section content, without markers.
+    /// Returns `None` if there is no data to render the section from, which leaves the section untouched.
+    fn render(&self) -> Option<String>;
+}
+
+/// A named section located in the readme.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    /// Byte range of the content between the markers.
+    pub content: Range<usize>,
+}
+
+/// The outcome of rendering the readme.
+pub struct Rendered {
+    pub readme: String,
+    /// Sections that were rendered.
+    pub updated: Vec<&'static str>,
+    /// Sections whose content changed.
+    pub stale: Vec<&'static str>,
+}
+
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{day}.rs")
+}
+
+#[must_use]
+pub fn begin_marker(name: &str) -> String {
+    format!("{MARKER_START}begin:{name}{MARKER_END}")
+}
+
+#[must_use]
+pub fn end_marker(name: &str) -> String {
+    format!("{MARKER_START}end:{name}{MARKER_END}")
+}
+
+enum Marker<'a> {
+    Begin(&'a str),
+    End(&'a str),
+}
+
+/// Find all section markers, returning their byte range in `readme`. Comments that are not section markers are skipped.
+fn find_markers(readme: &str) -> Vec<(Range<usize>, Marker<'_>)> {
+    readme
+        .match_indices(MARKER_START)
+        .filter_map(|(start, _)| {
+            let inner_start = start + MARKER_START.len();
+            let inner_len = readme[inner_start..].find(MARKER_END)?;
+            let inner = &readme[inner_start..inner_start + inner_len];
+            let end = inner_start + inner_len + MARKER_END.len();
+
+            let marker = if let Some(name) = inner.strip_prefix("begin:") {
+                Marker::Begin(name)
+            } else if let Some(name) = inner.strip_prefix("end:") {
+                Marker::End(name)
+            } else {
+                return None;
+            };
+
+            Some((start..end, marker))
+        })
+        .collect()
+}
+
+/// Locate all sections in the readme, validating that markers are balanced, not nested and not duplicated.
+pub fn locate_sections(readme: &str) -> Result<Vec<Section>, Error> {
+    let mut sections: Vec<Section> = vec![];
+    let mut open: Option<(&str, usize)> = None;
+
+    for (range, marker) in find_markers(readme) {
+        match (marker, open) {
+            (Marker::Begin(name), None) => {
+                if sections.iter().any(|s| s.name == name) {
+                    return Err(Error::Parser(format!(
+                        "section `{name}` occurs more than once in {README_PATH}."
+                    )));
+                }
+                open = Some((name, range.end));
+            }
+            (Marker::Begin(name), Some((outer, _))) => {
+                return Err(Error::Parser(format!(
+                    "section `{name}` begins inside of section `{outer}` in {README_PATH}, sections can not be nested."
+                )));
+            }
+            (Marker::End(name), Some((open_name, content_start))) if name == open_name => {
+                sections.push(Section {
+                    name: name.into(),
+                    content: content_start..range.start,
+                });
+                open = None;
+            }
+            (Marker::End(name), Some((open_name, _))) => {
+                return Err(Error::Parser(format!(
+                    "section `{open_name}` is closed by the end marker of section `{name}` in {README_PATH}."
+                )));
+            }
+            (Marker::End(name), None) => {
+                return Err(Error::Parser(format!(
+                    "section `{name}` ends without beginning in {README_PATH}."
+                )));
+            }
+        }
+    }
+
+    if let Some((name, _)) = open {
+        return Err(Error::Parser(format!(
+            "section `{name}` is never closed in {README_PATH}."
+        )));
+    }
+
+    Ok(sections)
+}
+
+/// Render the sections of `readme` that have a generator. Sections without a generator,
+/// and generators without a section, are skipped.
+pub fn render(readme: &str, generators: &[&dyn Generator]) -> Result<Rendered, Error> {
+    let sections = locate_sections(readme)?;
+
+    let mut replacements: Vec<(Range<usize>, String, &'static str)> = generators
+        .iter()
+        .filter_map(|generator| {
+            let section = sections.iter().find(|s| s.name == generator.name())?;
+            let content = generator.render()?;
+            Some((
+                section.content.clone(),
+                format!("\n{}\n", content.trim_matches('\n')),
+                generator.name(),
+            ))
+        })
+        .collect();
+
+    let updated = replacements.iter().map(|r| r.2).collect();
+    let stale = replacements
+        .iter()
+        .filter(|(range, content, _)| readme[range.clone()] != **content)
+        .map(|r| r.2)
+        .collect();
+
+    // replace back to front so earlier ranges stay valid.
+    replacements.sort_unstable_by_key(|r| std::cmp::Reverse(r.0.start));
+    let mut readme = readme.to_string();
+    for (range, content, _) in replacements {
+        readme.replace_range(range, &content);
+    }
+
+    Ok(Rendered {
+        readme,
+        updated,
+        stale,
+    })
+}
+
+/// Render the readme with `generators` and write it back if anything changed.
+pub fn update(generators: &[&dyn Generator]) -> Result<Rendered, Error> {
+    let readme = fs::read_to_string(README_PATH)?;
+    let rendered = render(&readme, generators)?;
+    if !rendered.stale.is_empty() {
+        fs::write(README_PATH, &rendered.readme)?;
+    }
+    Ok(rendered)
+}
+
+/// Render the readme with `generators` without writing it, to find out which sections are stale.
+pub fn check(generators: &[&dyn Generator]) -> Result<Rendered, Error> {
+    let readme = fs::read_to_string(README_PATH)?;
+    render(&readme, generators)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{begin_marker, end_marker, locate_sections, render, Generator};
+
+    struct MockGenerator(&'static str, Option<&'static str>);
+
+    impl Generator for MockGenerator {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn render(&self) -> Option<String> {
+            self.1.map(String::from)
+        }
+    }
+
+    fn section(name: &str, content: &str) -> String {
+        format!("{}{content}{}", begin_marker(name), end_marker(name))
+    }
+
+    #[test]
+    fn locates_sections() {
+        let readme = format!(
+            "# readme\n{}\n<!--- unrelated --->\n{}",
+            section("benchmarks", "\nfoo\n"),
+            section("notes", "")
+        );
+        let sections = locate_sections(&readme).unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "benchmarks");
+        assert_eq!(&readme[sections[0].content.clone()], "\nfoo\n");
+        assert_eq!(sections[1].name, "notes");
+        assert_eq!(&readme[sections[1].content.clone()], "");
+    }
+
+    #[test]
+    fn errors_for_unclosed_sections() {
+        let readme = format!("{}\nfoo", begin_marker("benchmarks"));
+        let err = locate_sections(&readme).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "section `benchmarks` is never closed in README.md."
+        );
+    }
+
+    #[test]
+    fn errors_for_unopened_sections() {
+        let readme = format!("foo\n{}", end_marker("benchmarks"));
+        assert!(locate_sections(&readme).is_err());
+    }
+
+    #[test]
+    fn errors_for_nested_sections() {
+        let readme = format!(
+            "{}{}{}",
+            begin_marker("benchmarks"),
+            section("charts", ""),
+            end_marker("benchmarks")
+        );
+        let err = locate_sections(&readme).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "section `charts` begins inside of section `benchmarks` in README.md, sections can not be nested."
+        );
+    }
+
+    #[test]
+    fn errors_for_interleaved_sections() {
+        let readme = format!(
+            "{}{}{}{}",
+            begin_marker("benchmarks"),
+            begin_marker("charts"),
+            end_marker("benchmarks"),
+            end_marker("charts")
+        );
+        assert!(locate_sections(&readme).is_err());
+    }
+
+    #[test]
+    fn errors_for_mismatched_sections() {
+        let readme = format!("{}{}", begin_marker("benchmarks"), end_marker("charts"));
+        assert!(locate_sections(&readme).is_err());
+    }
+
+    #[test]
+    fn errors_for_duplicate_sections() {
+        let readme = format!("{}{}", section("notes", ""), section("notes", ""));
+        assert!(locate_sections(&readme).is_err());
+    }
+
+    #[test]
+    fn renders_sections() {
+        let readme = format!(
+            "foo\n{}\nbar\n{}\n{}\nbaz",
+            section("benchmarks", "\nold\n"),
+            section("progress", ""),
+            section("notes", "\nkeep\n")
+        );
+        let rendered = render(
+            &readme,
+            &[
+                &MockGenerator("progress", Some("## Progress")),
+                &MockGenerator("benchmarks", Some("## Benchmarks\n")),
+                &MockGenerator("notes", None),
+                &MockGenerator("charts", Some("## Charts")),
+            ],
+        )
+        .unwrap();
+
+        let expected = format!(
+            "foo\n{}\nbar\n{}\n{}\nbaz",
+            section("benchmarks", "\n## Benchmarks\n"),
+            section("progress", "\n## Progress\n"),
+            section("notes", "\nkeep\n")
+        );
+        assert_eq!(rendered.readme, expected);
+        assert_eq!(rendered.updated, vec!["progress", "benchmarks"]);
+        assert_eq!(rendered.stale, vec!["progress", "benchmarks"]);
+    }
+
+    #[test]
+    fn detects_up_to_date_sections() {
+        let readme = section("benchmarks", "\n## Benchmarks\n");
+        let rendered = render(
+            &readme,
+            &[&MockGenerator("benchmarks", Some("## Benchmarks"))],
+        )
+        .unwrap();
+        assert_eq!(rendered.readme, readme);
+        assert_eq!(rendered.updated, vec!["benchmarks"]);
+        assert!(rendered.stale.is_empty());
+    }
+}
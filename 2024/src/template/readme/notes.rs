@@ -0,0 +1,112 @@
+//! Renders per-day notes written to `data/notes/DD.md`.
+
+use std::{env, fs};
+
+use super::{get_path_for_bin, Generator};
+use crate::template::{all_days, Day};
+
+pub struct Note {
+    pub day: Day,
+    pub content: String,
+}
+
+pub struct Notes {
+    pub notes: Vec<Note>,
+}
+
+impl Notes {
+    /// Read the notes of all days that have a non-empty notes file.
+    pub fn read_from_files() -> Self {
+        let cwd = env::current_dir().unwrap();
+        let notes = all_days()
+            .filter_map(|day| {
+                let path = cwd.join("data").join("notes").join(format!("{day}.md"));
+                let content = fs::read_to_string(path).ok()?;
+                let content = content.trim();
+                (!content.is_empty()).then(|| Note {
+                    day,
+                    content: content.to_string(),
+                })
+            })
+            .collect();
+        Notes { notes }
+    }
+}
+
+impl Generator for Notes {
+    fn name(&self) -> &'static str {
+        "notes"
+    }
+
+    fn render(&self) -> Option<String> {
+        (!self.notes.is_empty()).then(|| construct_notes("##", &self.notes))
+    }
+}
+
+fn construct_notes(prefix: &str, notes: &[Note]) -> String {
+    let mut lines: Vec<String> = vec![format!("{prefix} Notes")];
+
+    for note in notes {
+        lines.push(String::new());
+        lines.push(format!(
+            "{prefix}# [Day {}]({})",
+            note.day.into_inner(),
+            get_path_for_bin(note.day)
+        ));
+        lines.push(String::new());
+        lines.push(note.content.clone());
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Note, Notes};
+    use crate::{
+        day,
+        template::readme::{begin_marker, end_marker, render},
+    };
+
+    #[test]
+    fn skips_empty_notes() {
+        let readme = format!("{}\nkeep\n{}", begin_marker("notes"), end_marker("notes"));
+        let rendered = render(&readme, &[&Notes { notes: vec![] }]).unwrap();
+        assert_eq!(rendered.readme, readme);
+    }
+
+    #[test]
+    fn format_notes() {
+        let notes = Notes {
+            notes: vec![
+                Note {
+                    day: day!(1),
+                    content: "Sorted both lists.".into(),
+                },
+                Note {
+                    day: day!(3),
+                    content: "Hand-rolled scanner.\n\nNo regex.".into(),
+                },
+            ],
+        };
+        let readme = format!("{}{}", begin_marker("notes"), end_marker("notes"));
+        let s = render(&readme, &[&notes]).unwrap().readme;
+        let expected = [
+            "<!--- begin:notes --->",
+            "## Notes",
+            "",
+            "### [Day 1](./src/bin/01.rs)",
+            "",
+            "Sorted both lists.",
+            "",
+            "### [Day 3](./src/bin/03.rs)",
+            "",
+            "Hand-rolled scanner.",
+            "",
+            "No regex.",
+            "<!--- end:notes --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,213 @@
+//! Renders the star progress table.
+//! Stars are derived from the local submission history and puzzle titles from downloaded puzzles,
+//! so no session cookie or network access is required.
+
+use std::{fs, path::Path};
+
+use super::{get_path_for_bin, Generator};
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, aoc_cli, Day};
+
+pub struct ProgressTable {
+    pub progress: Vec<Progress>,
+    pub year: Option<u16>,
+}
+
+impl ProgressTable {
+    /// Collect progress from the submission history and downloaded puzzles.
+    /// Without any recorded submissions there is nothing to track yet, so the table stays empty.
+    pub fn new(submissions: &Submissions) -> Self {
+        let progress = if submissions.data.is_empty() {
+            vec![]
+        } else {
+            collect_progress(submissions)
+        };
+        ProgressTable {
+            progress,
+            year: aoc_cli::get_year(),
+        }
+    }
+}
+
+impl Generator for ProgressTable {
+    fn name(&self) -> &'static str {
+        "progress"
+    }
+
+    fn render(&self) -> Option<String> {
+        (!self.progress.is_empty()).then(|| construct_table("##", &self.progress, self.year))
+    }
+}
+
+/// Represents the progress on a single day.
+pub struct Progress {
+    pub day: Day,
+    pub title: Option<String>,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// Extract the puzzle title from a puzzle description downloaded by aoc-cli.
+/// The description starts with a heading like `## --- Day 1: Historian Hysteria ---`.
+fn parse_title(puzzle: &str, day: Day) -> Option<String> {
+    let prefix = format!("Day {}:", day.into_inner());
+    let line = puzzle.lines().find(|line| line.contains(&prefix))?;
+    let (_, title) = line.split_once(&prefix)?;
+    let title = title
+        .trim()
+        .trim_end_matches(['-', '\\', ' '])
+        .replace('\\', "");
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+fn read_title(day: Day) -> Option<String> {
+    let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()?;
+    parse_title(&puzzle, day)
+}
+
+/// Collect progress for every day that has been scaffolded, downloaded or submitted.
+fn collect_progress(submissions: &Submissions) -> Vec<Progress> {
+    all_days()
+        .filter(|day| {
+            Path::new(&get_path_for_bin(*day)).exists()
+                || Path::new(&aoc_cli::get_puzzle_path(*day)).exists()
+                || submissions.has_day(*day)
+        })
+        .map(|day| Progress {
+            day,
+            title: read_title(day),
+            part_1: submissions.has_star(day, 1),
+            part_2: submissions.has_star(day, 2),
+        })
+        .collect()
+}
+
+fn format_star(has_star: bool) -> &'static str {
+    if has_star {
+        "⭐"
+    } else {
+        " "
+    }
+}
+
+fn construct_table(prefix: &str, progress: &[Progress], year: Option<u16>) -> String {
+    let header = format!("{prefix} Progress");
+
+    let mut lines: Vec<String> = vec![
+        header,
+        String::new(),
+        "| Day | Puzzle | Part 1 | Part 2 |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    for entry in progress {
+        let path = get_path_for_bin(entry.day);
+        let puzzle = match (&entry.title, year) {
+            (Some(title), Some(year)) => format!(
+                "[{title}](https://adventofcode.com/{year}/day/{})",
+                entry.day.into_inner()
+            ),
+            (Some(title), None) => title.clone(),
+            (None, _) => "-".into(),
+        };
+
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} | {} |",
+            entry.day.into_inner(),
+            path,
+            puzzle,
+            format_star(entry.part_1),
+            format_star(entry.part_2)
+        ));
+    }
+
+    let stars = progress
+        .iter()
+        .map(|p| usize::from(p.part_1) + usize::from(p.part_2))
+        .sum::<usize>();
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, Progress, ProgressTable};
+    use crate::{
+        day,
+        template::readme::{begin_marker, end_marker, render},
+    };
+
+    fn get_mock_table(year: Option<u16>) -> ProgressTable {
+        ProgressTable {
+            progress: vec![
+                Progress {
+                    day: day!(1),
+                    title: Some("Historian Hysteria".into()),
+                    part_1: true,
+                    part_2: true,
+                },
+                Progress {
+                    day: day!(2),
+                    title: None,
+                    part_1: true,
+                    part_2: false,
+                },
+            ],
+            year,
+        }
+    }
+
+    #[test]
+    fn parses_titles() {
+        let puzzle = "## \\--- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is...";
+        assert_eq!(
+            parse_title(puzzle, day!(1)),
+            Some("Historian Hysteria".to_string())
+        );
+        assert_eq!(parse_title(puzzle, day!(2)), None);
+    }
+
+    #[test]
+    fn skips_empty_progress() {
+        let table = ProgressTable {
+            progress: vec![],
+            year: None,
+        };
+        let readme = format!("{}{}", begin_marker("progress"), end_marker("progress"));
+        assert!(render(&readme, &[&table]).unwrap().updated.is_empty());
+    }
+
+    #[test]
+    fn format_progress() {
+        let s = format!(
+            "foo\n{}\n{}\nbaz",
+            begin_marker("progress"),
+            end_marker("progress")
+        );
+        let s = render(&s, &[&get_mock_table(Some(2024))]).unwrap().readme;
+        let expected = [
+            "foo",
+            "<!--- begin:progress --->",
+            "## Progress",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 |",
+            "| :---: | :--- | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](./src/bin/02.rs) | - | ⭐ |   |",
+            "",
+            "**Stars: 3/50**",
+            "<!--- end:progress --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -4,46 +4,68 @@ use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    export::{self, DayResult, Format},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the solutions for `days_to_run`. With the [`Format::Table`] format, solution output is streamed to stdout,
+/// with any other format results are collected and printed once all solutions ran, optionally with `redact`ed answers.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    format: Format,
+    redact: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut results: Vec<DayResult> = Vec::with_capacity(days_to_run.len());
 
+    let is_streamed = format == Format::Table;
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
     all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+            if is_streamed {
+                if need_space {
+                    println!();
+                }
+                need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+                println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+                println!("------");
+            }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, is_streamed).unwrap();
 
             if output.is_empty() {
-                println!("Not solved.");
+                if is_streamed {
+                    println!("Not solved.");
+                }
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
+                results.push(DayResult {
+                    day,
+                    parts: child_commands::parse_results(&output),
+                });
             }
         });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = Timings { data: timings };
+
+    if let Some(rendered) = export::render(format, &results, redact) {
+        println!("{rendered}");
+    } else if is_timed {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    is_timed.then_some(timings)
 }
 
 #[allow(dead_code)]
@@ -68,7 +90,9 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        export::PartResult, matrix::Variant, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,8 +100,13 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. If `echo` is set, stdout of the solution is forwarded while it runs.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        echo: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -96,11 +125,43 @@ pub mod child_commands {
             args.push("--time");
         }
 
+        run_cargo(&args, &[], echo)
+    }
+
+    /// Run and time the solution bin for a given day, built with a matrix `variant`.
+    pub fn run_variant(day: Day, variant: &Variant, echo: bool) -> Result<Vec<String>, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(vec![]);
+        }
+
+        let day_padded = day.to_string();
+        let args = [
+            "run",
+            "--quiet",
+            "--bin",
+            &day_padded,
+            "--profile",
+            &variant.profile,
+            "--",
+            "--time",
+        ];
+
+        let target_dir = variant.target_dir();
+        let mut envs = vec![("CARGO_TARGET_DIR", target_dir.as_str())];
+        if let Some(rustflags) = &variant.rustflags {
+            envs.push(("RUSTFLAGS", rustflags));
+        }
+
+        run_cargo(&args, &envs, echo)
+    }
+
+    fn run_cargo(args: &[&str], envs: &[(&str, &str)], echo: bool) -> Result<Vec<String>, Error> {
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
+            .envs(envs.iter().copied())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -118,7 +179,9 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if echo {
+                println!("{line}");
+            }
             output.push(line);
         }
 
@@ -164,6 +227,89 @@ pub mod child_commands {
         timings
     }
 
+    /// Parse answers, timings and sample counts for each part from the output of a solution.
+    pub fn parse_results(output: &[String]) -> Vec<PartResult> {
+        let mut results: Vec<PartResult> = vec![];
+        let mut multiline_answer: Option<Vec<&str>> = None;
+
+        for raw_line in output {
+            // intermediate results are overwritten with a carriage return, only keep the final one.
+            let line = raw_line.rsplit('\r').next().unwrap_or_default();
+
+            let part = line
+                .strip_prefix("Part ")
+                .and_then(|l| l.split_once(':'))
+                .and_then(|(part, rest)| Some((part.parse::<u8>().ok()?, rest)));
+
+            let Some((part, rest)) = part else {
+                // lines following a `▼` marker are part of a multi-line answer.
+                if let Some(lines) = multiline_answer.as_mut() {
+                    lines.push(line);
+                }
+                continue;
+            };
+
+            flush_multiline_answer(&mut results, &mut multiline_answer);
+
+            let rest = strip_ansi(rest.trim());
+            let (answer, timing) = match rest.rfind(" (") {
+                Some(pos) if rest.ends_with(')') => {
+                    (rest[..pos].trim(), Some(&rest[pos + 2..rest.len() - 1]))
+                }
+                _ => (rest.trim(), None),
+            };
+
+            let (time, nanos, samples) = timing
+                .and_then(|t| {
+                    let (time, nanos) = parse_time(t)?;
+                    Some((Some(time.to_string()), nanos, parse_samples(t)))
+                })
+                .unwrap_or((None, 0_f64, 0));
+
+            let answer = match answer {
+                "✖" | "" => None,
+                "▼" => {
+                    multiline_answer = Some(vec![]);
+                    None
+                }
+                answer => Some(answer.to_string()),
+            };
+
+            results.push(PartResult {
+                part,
+                answer,
+                time,
+                nanos,
+                samples,
+            });
+        }
+
+        flush_multiline_answer(&mut results, &mut multiline_answer);
+        results
+    }
+
+    fn flush_multiline_answer(results: &mut [PartResult], lines: &mut Option<Vec<&str>>) {
+        if let (Some(lines), Some(result)) = (lines.take(), results.last_mut()) {
+            let answer = lines.join("\n").trim_end().to_string();
+            result.answer = Some(answer);
+        }
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        [ANSI_BOLD, ANSI_ITALIC, ANSI_RESET]
+            .iter()
+            .fold(s.to_string(), |s, code| s.replace(code, ""))
+    }
+
+    fn parse_samples(timing: &str) -> u128 {
+        timing
+            .split('@')
+            .nth(1)
+            .and_then(|s| s.trim().split(' ').next())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1)
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +320,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +351,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_results};
 
         use crate::day;
 
@@ -253,5 +399,46 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_results() {
+            let res = parse_results(&[
+                "Part 1: \x1b[1m42\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (74.13ns @ 100000 samples)".into(),
+                "Part 2: ✖             ".into(),
+                "".into(),
+            ]);
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].part, 1);
+            assert_eq!(res[0].answer.as_deref(), Some("42"));
+            assert_eq!(res[0].time.as_deref(), Some("74.13ns"));
+            assert_eq!(res[0].samples, 100000);
+            assert_approx_eq!(res[0].nanos, 74.13_f64);
+            assert_eq!(res[1].part, 2);
+            assert_eq!(res[1].answer, None);
+            assert_eq!(res[1].time, None);
+        }
+
+        #[test]
+        fn parses_untimed_results() {
+            let res = parse_results(&["Part 1: \x1b[1m(1, 2)\x1b[0m (19.0ns)".into()]);
+            assert_eq!(res[0].answer.as_deref(), Some("(1, 2)"));
+            assert_eq!(res[0].time.as_deref(), Some("19.0ns"));
+            assert_eq!(res[0].samples, 1);
+        }
+
+        #[test]
+        fn parses_multiline_results() {
+            let res = parse_results(&[
+                "Part 1: ▼ \rPart 1: ▼  (1.0ms @ 10 samples)".into(),
+                "#..".into(),
+                "..#".into(),
+                "".into(),
+                "Part 2: \x1b[1m7\x1b[0m (2.0ms @ 10 samples)".into(),
+            ]);
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].answer.as_deref(), Some("#..\n..#"));
+            assert_eq!(res[0].time.as_deref(), Some("1.0ms"));
+            assert_eq!(res[1].answer.as_deref(), Some("7"));
+        }
     }
 }
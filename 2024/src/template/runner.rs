@@ -1,4 +1,6 @@
-/// Encapsulates code that interacts with solution functions.
+//! Encapsulates code that interacts with solution functions.
+
+use std::cell::Cell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
@@ -6,17 +8,45 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::debug;
+use crate::template::submissions::{Submission, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
-
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+#[cfg(feature = "viz")]
+use crate::visualizer;
+
+/// Run a solution part. The part receives the input as `&str` or as any type created from it,
+/// e.g. [`crate::parse::Input`].
+///
+/// With `--debug-frames`, the frames of the first run are recorded, see [`crate::template::debug`].
+/// With `--viz`, the first run is animated, see [`crate::visualizer`].
+pub fn run_part<'a, I: From<&'a str> + Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: &'a str,
+    day: Day,
+    part: u8,
+) {
+    let input = I::from(input);
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let frames = Cell::new(None);
+
+    debug::start(day, part);
+    #[cfg(feature = "viz")]
+    visualizer::arm(day, part);
+    let (result, duration, samples) = run_timed(func, input, |result| {
+        frames.set(debug::stop());
+        #[cfg(feature = "viz")]
+        visualizer::disarm();
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if let Some((count @ 1.., dir)) = frames.take() {
+        println!("{count} debug frames written to {}", dir.display());
+    }
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
@@ -128,6 +158,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///
+/// The response is recorded in the submission history, which backs the progress table in the readme.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -161,5 +193,32 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let submission = aoc_cli::submit(day, part, &answer);
+
+    if let Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) = &submission {
+        record_submission(day, part, answer, output);
+    }
+
+    Some(submission)
+}
+
+fn record_submission(day: Day, part: u8, answer: String, output: &Output) {
+    let response = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut submissions = Submissions::read_from_file();
+    submissions.data.push(Submission {
+        day,
+        part,
+        answer,
+        verdict: Verdict::from_response(&response),
+    });
+
+    if let Err(e) = submissions.store_file() {
+        eprintln!("Failed to record submission: {e}");
+    }
 }
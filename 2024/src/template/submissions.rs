@@ -0,0 +1,317 @@
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The outcome of submitting an answer, as reported by the puzzle server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Derive a verdict from the text aoc-cli prints after submitting an answer.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if response.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether this verdict means the part has been awarded a star.
+    pub fn is_star(self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "already_solved" => Ok(Verdict::AlreadySolved),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// Represents a single answer submitted for one part of a day.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Represents the history of submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns empty submissions.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Whether a star has been awarded for the given day and part.
+    pub fn has_star(&self, day: Day, part: u8) -> bool {
+        self.data
+            .iter()
+            .any(|s| s.day == day && s.part == part && s.verdict.is_star())
+    }
+
+    /// Count the stars awarded across all days.
+    pub fn total_stars(&self) -> usize {
+        self.data
+            .iter()
+            .filter(|s| s.verdict.is_star())
+            .map(|s| (s.day, s.part))
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    /// Whether any answer has been submitted for the given day.
+    pub fn has_day(&self, day: Day) -> bool {
+        self.data.iter().any(|s| s.day == day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| Verdict::from_str(v).ok())
+            .ok_or("Expected submission.verdict to be a known verdict.")?;
+
+        Ok(Submission {
+            day,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Submission, Submissions, Verdict};
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "41".into(),
+                    verdict: Verdict::Incorrect,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "42".into(),
+                    verdict: Verdict::Correct,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 2,
+                    answer: "7".into(),
+                    verdict: Verdict::AlreadySolved,
+                },
+                Submission {
+                    day: day!(2),
+                    part: 1,
+                    answer: "1".into(),
+                    verdict: Verdict::Unknown,
+                },
+            ],
+        }
+    }
+
+    mod verdict {
+        use super::Verdict;
+
+        #[test]
+        fn parses_responses() {
+            assert_eq!(
+                Verdict::from_response("That's the right answer! You are one gold star closer."),
+                Verdict::Correct
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer; your answer is too low."),
+                Verdict::Incorrect
+            );
+            assert_eq!(
+                Verdict::from_response(
+                    "You don't seem to be solving the right level.  Did you already complete it?"
+                ),
+                Verdict::AlreadySolved
+            );
+            assert_eq!(
+                Verdict::from_response("You gave an answer too recently."),
+                Verdict::Unknown
+            );
+        }
+    }
+
+    mod stars {
+        use super::get_mock_submissions;
+        use crate::day;
+
+        #[test]
+        fn counts_stars_once_per_part() {
+            let submissions = get_mock_submissions();
+            assert!(submissions.has_star(day!(1), 1));
+            assert!(submissions.has_star(day!(1), 2));
+            assert!(!submissions.has_star(day!(2), 1));
+            assert_eq!(submissions.total_stars(), 2);
+        }
+    }
+
+    mod serialization {
+        use super::get_mock_submissions;
+        use crate::template::submissions::{Submissions, Verdict};
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn roundtrips_submissions() {
+            let json = JsonValue::from(get_mock_submissions()).stringify().unwrap();
+            let submissions = Submissions::try_from(json).unwrap();
+            assert_eq!(submissions.data.len(), 4);
+            assert_eq!(submissions.data[1].answer, "42");
+            assert_eq!(submissions.data[2].part, 2);
+            assert_eq!(submissions.data[2].verdict, Verdict::AlreadySolved);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_invalid_part() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct" }] }"#
+                    .to_string();
+            Submissions::try_from(json).unwrap();
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::fingerprint::Fingerprint;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
@@ -21,7 +22,22 @@ pub struct Timings {
     pub data: Vec<Timing>,
 }
 
-impl Timings {
+/// Represents benchmark times recorded on a single machine.
+#[derive(Clone, Debug, Default)]
+pub struct MachineTimings {
+    pub fingerprint: Fingerprint,
+    pub timings: Timings,
+}
+
+/// Represents benchmark times for every machine they were recorded on.
+/// Timings of different machines are kept apart, since they are not comparable.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct TimingsStore {
+    pub machines: Vec<MachineTimings>,
+}
+
+impl TimingsStore {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
@@ -33,10 +49,44 @@ impl Timings {
     pub fn read_from_file() -> Self {
         fs::read_to_string(TIMINGS_FILE_PATH)
             .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
+            .and_then(TimingsStore::try_from)
             .unwrap_or_default()
     }
 
+    /// Timings recorded on the machine with the given fingerprint.
+    pub fn get(&self, fingerprint: &Fingerprint) -> Option<&MachineTimings> {
+        self.machines.iter().find(|m| &m.fingerprint == fingerprint)
+    }
+
+    /// Timings recorded on the machine whose id starts with `id`.
+    /// Returns `None` if no machine or more than one machine matches.
+    pub fn find(&self, id: &str) -> Option<&MachineTimings> {
+        let mut matches = self
+            .machines
+            .iter()
+            .filter(|m| m.fingerprint.id().starts_with(id));
+        let machine = matches.next()?;
+        matches.next().is_none().then_some(machine)
+    }
+
+    /// Merge `new` into the timings of the machine with the given fingerprint,
+    /// leaving the timings of other machines untouched.
+    pub fn merge(&self, fingerprint: &Fingerprint, new: &Timings) -> Self {
+        let mut machines = self.machines.clone();
+
+        match machines.iter_mut().find(|m| &m.fingerprint == fingerprint) {
+            Some(machine) => machine.timings = machine.timings.merge(new),
+            None => machines.push(MachineTimings {
+                fingerprint: fingerprint.clone(),
+                timings: Timings::default().merge(new),
+            }),
+        }
+
+        TimingsStore { machines }
+    }
+}
+
+impl Timings {
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -51,7 +101,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,6 +117,110 @@ impl Timings {
     }
 }
 
+/// Format a duration in nanoseconds with the largest fitting unit.
+pub fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{:.2}s", n / 1e9),
+        n if n >= 1e6 => format!("{:.2}ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2}µs", n / 1e3),
+        n => format!("{n:.0}ns"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<TimingsStore> for JsonValue {
+    fn from(value: TimingsStore) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "machines".into(),
+            JsonValue::Array(value.machines.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingsStore {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_object = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        // timings stored before fingerprints were introduced have no machine information.
+        if json_object.contains_key("data") {
+            return Ok(TimingsStore {
+                machines: vec![MachineTimings {
+                    fingerprint: Fingerprint::default(),
+                    timings: Timings::try_from(value)?,
+                }],
+            });
+        }
+
+        let json_machines = json_object
+            .get("machines")
+            .ok_or("expected JSON document to have key `machines`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.machines` to be an array.")?;
+
+        Ok(TimingsStore {
+            machines: json_machines
+                .iter()
+                .map(MachineTimings::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MachineTimings> for JsonValue {
+    fn from(value: &MachineTimings) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("fingerprint".into(), JsonValue::from(&value.fingerprint));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MachineTimings {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine timings to be a JSON object.")?;
+
+        let fingerprint = json
+            .get("fingerprint")
+            .ok_or("Expected machine timings to have a fingerprint.")
+            .map(Fingerprint::try_from)??;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected machine timings.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(MachineTimings {
+            fingerprint,
+            timings: Timings { data },
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -181,7 +335,15 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{format_nanos, Timing, Timings};
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(12.0), "12ns");
+        assert_eq!(format_nanos(1_500.0), "1.50µs");
+        assert_eq!(format_nanos(2_500_000.0), "2.50ms");
+        assert_eq!(format_nanos(3e9), "3.00s");
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -285,7 +447,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -299,7 +461,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -313,7 +475,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -381,4 +543,91 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+    mod store {
+        use crate::{
+            day,
+            template::fingerprint::Fingerprint,
+            template::timings::{Timing, Timings, TimingsStore},
+        };
+        use tinyjson::JsonValue;
+
+        use super::get_mock_timings;
+
+        fn get_mock_fingerprint(profile: &str) -> Fingerprint {
+            Fingerprint {
+                cpu: "Apple M1".into(),
+                cores: 8,
+                rustc: "rustc 1.83.0".into(),
+                profile: profile.into(),
+                features: vec![],
+            }
+        }
+
+        #[test]
+        fn handles_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let store = TimingsStore::try_from(json).unwrap();
+            assert_eq!(store.machines.len(), 1);
+            assert!(store.machines[0].fingerprint.is_unknown());
+            assert_eq!(store.machines[0].timings.data.len(), 1);
+        }
+
+        #[test]
+        fn keeps_machines_apart() {
+            let release = get_mock_fingerprint("release");
+            let dhat = get_mock_fingerprint("dhat");
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: None,
+                    part_2: None,
+                    total_nanos: 0_f64,
+                }],
+            };
+
+            let store = TimingsStore::default()
+                .merge(&release, &get_mock_timings())
+                .merge(&dhat, &other);
+
+            assert_eq!(store.machines.len(), 2);
+            assert_eq!(store.get(&release).unwrap().timings.data.len(), 3);
+            assert_eq!(
+                store.get(&release).unwrap().timings.data[0].total_nanos,
+                3e+10
+            );
+            assert_eq!(store.get(&dhat).unwrap().timings.data.len(), 1);
+
+            let merged = store.merge(&release, &other);
+            assert_eq!(merged.machines.len(), 2);
+            assert_eq!(
+                merged.get(&release).unwrap().timings.data[0].total_nanos,
+                0_f64
+            );
+        }
+
+        #[test]
+        fn finds_machines_by_id() {
+            let release = get_mock_fingerprint("release");
+            let store = TimingsStore::default()
+                .merge(&release, &get_mock_timings())
+                .merge(&get_mock_fingerprint("dhat"), &get_mock_timings());
+
+            let id = release.id();
+            assert_eq!(store.find(&id).unwrap().fingerprint, release);
+            assert_eq!(store.find(&id[..4]).unwrap().fingerprint, release);
+            assert!(store.find("").is_none());
+            assert!(store.find("not-an-id").is_none());
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let release = get_mock_fingerprint("release");
+            let store = TimingsStore::default().merge(&release, &get_mock_timings());
+            let json = JsonValue::from(store).stringify().unwrap();
+            let parsed = TimingsStore::try_from(json).unwrap();
+            assert_eq!(parsed.machines.len(), 1);
+            assert_eq!(parsed.machines[0].fingerprint, release);
+            assert_eq!(parsed.machines[0].timings.data.len(), 3);
+        }
+    }
 }
@@ -0,0 +1,165 @@
+//! Enums for the cells of puzzle maps, where every variant is drawn as one character.
+//!
+//! The [`tiles!`](crate::tiles) macro declares such an enum together with the mapping in both directions:
+//!
+//! ```ignore
+//! advent_of_code::tiles! {
+//!     /// A cell of the warehouse.
+//!     pub enum Elem {
+//!         Wall = '#',
+//!         Empty = '.',
+//!         Box = 'O',
+//!     }
+//! }
+//!
+//! let grid = Grid::<Elem>::parse_tiles(input)?;
+//! assert_eq!(Elem::try_from('#'), Ok(Elem::Wall));
+//! assert_eq!(grid.to_string(), input);
+//! ```
+//!
+//! The enum derives `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq` and `Hash`, further attributes are passed on.
+//! It converts from a `char` with `TryFrom`, failing with an [`UnknownTileError`], back with `Display`
+//! or `From`, and implements [`Tile`], which lists all variants.
+
+use std::{error::Error, fmt::Display};
+
+pub trait Tile: Copy + TryFrom<char, Error = UnknownTileError> + Display + 'static {
+    /// Every variant, in the order they are declared.
+    const ALL: &'static [Self];
+
+    /// The character the tile is drawn as.
+    fn to_char(self) -> char;
+}
+
+/// An error which can be returned when a character is not one of the tiles of an enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownTileError {
+    /// The name of the enum.
+    pub tile: &'static str,
+    pub found: char,
+    /// The characters of all tiles.
+    pub expected: &'static str,
+}
+
+impl Error for UnknownTileError {}
+
+impl Display for UnknownTileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} is not a tile of {}, expected one of {:?}",
+            self.found, self.tile, self.expected
+        )
+    }
+}
+
+/// Declares an enum of tiles drawn as characters, see [`crate::tile`].
+#[macro_export]
+macro_rules! tiles {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $char:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $( $(#[$variant_meta])* $variant ),+
+        }
+
+        impl $crate::tile::Tile for $name {
+            const ALL: &'static [Self] = &[$( $name::$variant ),+];
+
+            fn to_char(self) -> char {
+                match self {
+                    $( $name::$variant => $char ),+
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<char> for $name {
+            type Error = $crate::tile::UnknownTileError;
+
+            fn try_from(value: char) -> Result<Self, Self::Error> {
+                match value {
+                    $( $char => Ok($name::$variant), )+
+                    found => Err($crate::tile::UnknownTileError {
+                        tile: stringify!($name),
+                        found,
+                        expected: concat!($( $char ),+),
+                    }),
+                }
+            }
+        }
+
+        impl ::std::convert::From<$name> for char {
+            fn from(value: $name) -> char {
+                $crate::tile::Tile::to_char(value)
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Write::write_char(f, $crate::tile::Tile::to_char(*self))
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tile, UnknownTileError};
+    use crate::grid::{Grid, TryParseGridError};
+
+    crate::tiles! {
+        /// A cell of a warehouse.
+        #[derive(PartialOrd, Ord)]
+        enum Elem {
+            Wall = '#',
+            Empty = '.',
+            /// Pushed around by the robot.
+            Box = 'O',
+        }
+    }
+
+    #[test]
+    fn maps_chars() {
+        assert_eq!(Elem::try_from('O'), Ok(Elem::Box));
+        assert_eq!(char::from(Elem::Wall), '#');
+        assert_eq!(Elem::Empty.to_string(), ".");
+        assert_eq!(Elem::ALL, &[Elem::Wall, Elem::Empty, Elem::Box]);
+        assert!(Elem::Wall < Elem::Box);
+
+        let error = Elem::try_from('@').unwrap_err();
+        assert_eq!(
+            error,
+            UnknownTileError {
+                tile: "Elem",
+                found: '@',
+                expected: "#.O",
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "'@' is not a tile of Elem, expected one of \"#.O\""
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        let input = "####\n#.O#\n####";
+        let grid = Grid::<Elem>::parse_tiles(input).unwrap();
+        assert_eq!(grid[(2, 1)], Elem::Box);
+        assert_eq!(grid.to_string(), input);
+
+        assert_eq!(
+            Grid::<Elem>::parse_tiles("##\n#@"),
+            Err(TryParseGridError::Cell {
+                x: 1,
+                y: 1,
+                error: Elem::try_from('@').unwrap_err(),
+            })
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Terminal animations of simulations, e.g. a guard walking a map or robots pushing boxes around.
+//!
+//! A part creates a [`Visualizer`] and hands it a frame every step. With `cargo solve <day> --viz`, the
+//! first run of each part is animated in the terminal: frames are drawn in place with ANSI escape codes,
+//! cropped to the terminal and paced to a frame rate, 20 per second or the one given with `--fps <n>`.
+//! While animating, `space` pauses, `n` steps one frame while paused, `+` and `-` double or halve the
+//! frame rate, and `q` stops animating and lets the part finish.
+//!
+//! The animation only exists with the `viz` feature, which `--viz` turns on. Without it, a [`Visualizer`]
+//! is empty and never calls the closures that render frames, so it costs nothing in benchmarks.
+
+#[cfg(feature = "viz")]
+use std::{
+    env,
+    fs::File,
+    io::{self, IsTerminal, Read, Write},
+    process::Command,
+    sync::{Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "viz")]
+use crate::template::Day;
+
+#[derive(Debug, Default)]
+pub struct Visualizer {
+    #[cfg(feature = "viz")]
+    animation: Option<Animation>,
+}
+
+impl Visualizer {
+    /// A visualizer that animates if the part runs with `--viz`, and does nothing otherwise.
+    pub fn new() -> Self {
+        Visualizer {
+            #[cfg(feature = "viz")]
+            animation: Animation::start(),
+        }
+    }
+
+    /// Whether frames are shown. Only worth checking to skip work that frames need.
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        #[cfg(feature = "viz")]
+        return self.animation.is_some();
+        #[cfg(not(feature = "viz"))]
+        false
+    }
+
+    /// Show the frame built by `render`, cropped to the terminal from the top left.
+    #[inline(always)]
+    pub fn frame(&mut self, render: impl FnOnce() -> String) {
+        self.show(None, render);
+    }
+
+    /// Show the frame built by `render`, cropped to a window around column `x` of line `y` if it is
+    /// larger than the terminal.
+    #[inline(always)]
+    pub fn frame_around(&mut self, (x, y): (usize, usize), render: impl FnOnce() -> String) {
+        self.show(Some((x, y)), render);
+    }
+
+    #[cfg(feature = "viz")]
+    fn show(&mut self, focus: Option<(usize, usize)>, render: impl FnOnce() -> String) {
+        if let Some(animation) = &mut self.animation {
+            if !animation.show(&render(), focus) {
+                self.animation = None;
+            }
+        }
+    }
+
+    #[cfg(not(feature = "viz"))]
+    #[inline(always)]
+    fn show(&mut self, _focus: Option<(usize, usize)>, _render: impl FnOnce() -> String) {}
+}
+
+/// The part whose first run is animated, set by the runner.
+#[cfg(feature = "viz")]
+static ARMED: Mutex<Option<(Day, u8)>> = Mutex::new(None);
+
+/// Let visualizers of `part` animate until [`disarm`] is called.
+#[cfg(feature = "viz")]
+pub(crate) fn arm(day: Day, part: u8) {
+    *ARMED.lock().unwrap_or_else(PoisonError::into_inner) = Some((day, part));
+}
+
+#[cfg(feature = "viz")]
+pub(crate) fn disarm() {
+    *ARMED.lock().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+#[cfg(feature = "viz")]
+#[derive(Debug)]
+struct Animation {
+    title: String,
+    fps: u32,
+    paused: bool,
+    frames: usize,
+    /// The terminal size as `(rows, columns)`, checked again every second.
+    size: (usize, usize),
+    size_checked: Instant,
+    keys: Option<Keys>,
+}
+
+#[cfg(feature = "viz")]
+impl Animation {
+    const MAX_FPS: u32 = 1000;
+
+    fn start() -> Option<Self> {
+        let (day, part) = (*ARMED.lock().unwrap_or_else(PoisonError::into_inner))?;
+        let args = env::args().collect::<Vec<_>>();
+        if !args.iter().any(|x| x == "--viz") {
+            return None;
+        }
+        if !io::stdout().is_terminal() {
+            eprintln!("--viz needs a terminal, not animating.");
+            return None;
+        }
+
+        let fps = args
+            .iter()
+            .position(|x| x == "--fps")
+            .and_then(|i| args.get(i + 1)?.parse().ok())
+            .unwrap_or(20)
+            .clamp(1, Self::MAX_FPS);
+
+        // switch to the alternate screen and hide the cursor.
+        print!("\x1b[?1049h\x1b[?25l");
+        Some(Animation {
+            title: format!("Day {day} · Part {part}"),
+            fps,
+            paused: false,
+            frames: 0,
+            size: terminal_size(),
+            size_checked: Instant::now(),
+            keys: Keys::open(),
+        })
+    }
+
+    /// Draw `frame` and wait for the next one, handling keys. Returns `false` when asked to stop.
+    fn show(&mut self, frame: &str, focus: Option<(usize, usize)>) -> bool {
+        if self.size_checked.elapsed() > Duration::from_secs(1) {
+            self.size = terminal_size();
+            self.size_checked = Instant::now();
+        }
+        self.frames += 1;
+        self.draw(frame, focus);
+
+        let deadline = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            for key in self.keys.as_mut().map(Keys::read).unwrap_or_default() {
+                match key {
+                    b' ' => self.paused = !self.paused,
+                    b'n' if self.paused => return true,
+                    b'+' => self.fps = (self.fps * 2).min(Self::MAX_FPS),
+                    b'-' => self.fps = (self.fps / 2).max(1),
+                    b'q' => return false,
+                    _ => continue,
+                }
+                self.draw_status();
+            }
+
+            let now = Instant::now();
+            if !self.paused && now >= deadline {
+                return true;
+            }
+            let wait = Duration::from_millis(10);
+            thread::sleep(if self.paused {
+                wait
+            } else {
+                wait.min(deadline - now)
+            });
+        }
+    }
+
+    fn status(&self) -> String {
+        let state = if self.paused { " · paused" } else { "" };
+        let status = format!(
+            "{} · frame {} · {} fps{state} · space: pause, n: step, +/-: speed, q: quit",
+            self.title, self.frames, self.fps
+        );
+        // in reverse video, clearing the rest of the line.
+        let visible = status.chars().take(self.size.1).collect::<String>();
+        format!("\x1b[7m{visible}\x1b[0m\x1b[K")
+    }
+
+    fn draw_status(&self) {
+        print!("\x1b[H{}", self.status());
+        let _ = io::stdout().flush();
+    }
+
+    fn draw(&self, frame: &str, focus: Option<(usize, usize)>) {
+        let (rows, columns) = self.size;
+        let mut out = format!("\x1b[H{}", self.status());
+        // without a newline after the last line, so the terminal never scrolls.
+        for line in crop(frame, focus, rows.saturating_sub(1), columns) {
+            out.push('\n');
+            out.push_str(line);
+            out.push_str("\x1b[K");
+        }
+        out.push_str("\x1b[J");
+
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+#[cfg(feature = "viz")]
+impl Drop for Animation {
+    fn drop(&mut self) {
+        // show the cursor and leave the alternate screen.
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Key presses read from the terminal, which is put into non-canonical mode so keys arrive without
+/// `enter` and reads do not block. The previous mode is restored when dropped.
+#[cfg(feature = "viz")]
+#[derive(Debug)]
+struct Keys {
+    tty: File,
+    saved: String,
+}
+
+#[cfg(feature = "viz")]
+impl Keys {
+    fn open() -> Option<Self> {
+        let saved = stty(&["-g"])?;
+        let tty = File::open("/dev/tty").ok()?;
+        stty(&["-icanon", "-echo", "min", "0", "time", "0"])?;
+        Some(Keys {
+            tty,
+            saved: saved.trim().to_string(),
+        })
+    }
+
+    fn read(&mut self) -> Vec<u8> {
+        let mut buf = [0; 16];
+        match self.tty.read(&mut buf) {
+            Ok(n) => buf[..n].to_vec(),
+            Err(_) => vec![],
+        }
+    }
+}
+
+#[cfg(feature = "viz")]
+impl Drop for Keys {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Run `stty` on the terminal, returning its output if it succeeded.
+#[cfg(feature = "viz")]
+fn stty(args: &[&str]) -> Option<String> {
+    let tty = File::open("/dev/tty").ok()?;
+    let output = Command::new("stty").args(args).stdin(tty).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The terminal size as `(rows, columns)`, 24 × 80 if it cannot be found out.
+#[cfg(feature = "viz")]
+fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .and_then(|size| {
+            let (rows, columns) = size.trim().split_once(' ')?;
+            Some((rows.parse().ok()?, columns.parse().ok()?))
+        })
+        .unwrap_or((24, 80))
+}
+
+/// The part of `frame` that fits into `rows` × `columns`: a window around `focus`, `(column, line)`,
+/// or the top left without one. The window stays within the frame.
+#[cfg(any(feature = "viz", test))]
+fn crop(frame: &str, focus: Option<(usize, usize)>, rows: usize, columns: usize) -> Vec<&str> {
+    let lines = frame.lines().collect::<Vec<_>>();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let (x, y) = focus.unwrap_or((0, 0));
+    let window_start = |focus: usize, size: usize, len: usize| {
+        focus.saturating_sub(size / 2).min(len.saturating_sub(size))
+    };
+    let (top, left) = (
+        window_start(y, rows, lines.len()),
+        window_start(x, columns, width),
+    );
+
+    let byte_offset = |line: &str, chars: usize| {
+        line.char_indices()
+            .nth(chars)
+            .map_or(line.len(), |(i, _)| i)
+    };
+    lines
+        .into_iter()
+        .skip(top)
+        .take(rows)
+        .map(|line| {
+            let line = &line[byte_offset(line, left)..];
+            &line[..byte_offset(line, columns)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crop, Visualizer};
+
+    #[test]
+    fn crops_frames() {
+        let frame = "abcdef\nghijkl\nmnopqr\nstuvwx";
+        assert_eq!(crop(frame, None, 2, 3), vec!["abc", "ghi"]);
+        assert_eq!(crop(frame, Some((3, 2)), 2, 3), vec!["ijk", "opq"]);
+        // the window stays within the frame.
+        assert_eq!(crop(frame, Some((5, 3)), 2, 4), vec!["opqr", "uvwx"]);
+        assert_eq!(
+            crop(frame, Some((5, 3)), 10, 10),
+            frame.lines().collect::<Vec<_>>()
+        );
+        assert_eq!(crop("→↓←\n↑", Some((2, 0)), 1, 2), vec!["↓←"]);
+    }
+
+    #[test]
+    fn does_nothing_unless_asked() {
+        let mut viz = Visualizer::new();
+        assert!(!viz.is_active());
+        viz.frame(|| unreachable!("frames are not rendered without --viz"));
+    }
+}